@@ -0,0 +1,72 @@
+//! Compares cold-start time and memory of loading the Pokédex from the
+//! precompiled snapshot against parsing all of the included CSV data.
+//!
+//! Run with `cargo bench --bench load_time`. Each loader runs in a fresh child
+//! process so that the measurements are not affected by one another. Resident
+//! memory is only reported on Linux.
+
+extern crate vdex;
+extern crate veekun;
+
+use std::collections::HashMap;
+use std::env;
+use std::process::Command;
+use std::time::Instant;
+use vdex::DataSource;
+use vdex::Pokedex;
+use veekun::data as vdata;
+
+const RUNS: usize = 5;
+
+/// Peak resident set size of this process in KiB, if available.
+fn peak_rss_kib() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|l| l.starts_with("VmHWM:"))?;
+    line.split_whitespace().nth(1)?.parse().ok()
+}
+
+/// All of the included CSV data as a `Memory` source, which bypasses the
+/// snapshot without adding disk I/O to the measurement.
+fn csv_source() -> DataSource {
+    let files: HashMap<_, _> = vdata::files::ALL.iter().map(|&file| {
+        (file.to_string(), vdata::get(file).unwrap().as_bytes().to_vec())
+    }).collect();
+    DataSource::Memory(files)
+}
+
+/// Load the Pokédex once and print "<milliseconds> <KiB>".
+fn child(mode: &str) {
+    let source = match mode {
+        "snapshot" => DataSource::Embedded,
+        "csv" => csv_source(),
+        _ => panic!("Unknown mode: {}", mode),
+    };
+    let start = Instant::now();
+    let dex = Pokedex::from_source(&source).unwrap();
+    let elapsed = start.elapsed();
+    drop(source);
+    std::hint::black_box(&dex);
+    println!("{} {}", elapsed.as_secs_f64() * 1000.0,
+             peak_rss_kib().unwrap_or(0));
+}
+
+fn main() {
+    if let Some(mode) = env::args().nth(1).filter(|a| !a.starts_with('-')) {
+        return child(&mode);
+    }
+    let exe = env::current_exe().unwrap();
+    println!("{:<10} {:>10} {:>15}", "source", "time (ms)", "peak RSS (KiB)");
+    for mode in &["csv", "snapshot"] {
+        let mut times = Vec::new();
+        let mut rss = 0;
+        for _ in 0..RUNS {
+            let output = Command::new(&exe).arg(mode).output().unwrap();
+            let stdout = String::from_utf8(output.stdout).unwrap();
+            let mut fields = stdout.split_whitespace();
+            times.push(fields.next().unwrap().parse::<f64>().unwrap());
+            rss = rss.max(fields.next().unwrap().parse::<u64>().unwrap());
+        }
+        times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        println!("{:<10} {:>10.1} {:>15}", mode, times[RUNS / 2], rss);
+    }
+}
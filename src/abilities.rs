@@ -1,5 +1,6 @@
 use crate::enums::*;
 use crate::FromVeekun;
+use crate::versions::Generation;
 
 /// An ability provides a passive effect in battle or in the overworld.
 ///
@@ -179,6 +180,19 @@ pub enum Ability {
     Teravolt,
 }
 
+impl Ability {
+    /// The generation the ability was introduced.
+    pub fn generation(self) -> Generation {
+        if self.repr() <= Ability::AirLock.repr() {
+            Generation::III
+        } else if self.repr() <= Ability::BadDreams.repr() {
+            Generation::IV
+        } else {
+            Generation::V
+        }
+    }
+}
+
 impl Default for Ability {
     fn default() -> Self { Ability::Cacophony }
 }
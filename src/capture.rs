@@ -0,0 +1,361 @@
+//! Capture probability of wild Pokémon.
+//!
+//! > [*[From
+//! > Bulbapedia:]*](https://bulbapedia.bulbagarden.net/wiki/Catch_rate)
+//! > The catch rate (Japanese: 捕捉率 capture rate) is a hidden value of each
+//! > species . . . [which] determines how easy it is to catch that Pokémon.
+//! > . . . The chance of catching a Pokémon depends on its catch rate, its
+//! > current HP, the type of Poké Ball used, and status conditions.
+//!
+//! Each generation uses a different algorithm; see `Capture::probability`.
+//! The critical capture of Generation V, which depends on the number of
+//! Pokémon caught, and the dark grass modifier are not modeled.
+
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter};
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use crate::Enum;
+use crate::items::ItemId;
+use crate::moves::Ailment;
+use crate::pokemon::{OneOrTwo, Pokemon, Species};
+use crate::Type;
+use crate::versions::Generation;
+use self::balls::*;
+
+/// The item IDs of the Poké Balls.
+pub mod balls {
+    use crate::items::ItemId;
+
+    pub const MASTER_BALL: ItemId = ItemId(1);
+    pub const ULTRA_BALL: ItemId = ItemId(2);
+    pub const GREAT_BALL: ItemId = ItemId(3);
+    pub const POKE_BALL: ItemId = ItemId(4);
+    pub const SAFARI_BALL: ItemId = ItemId(5);
+    pub const NET_BALL: ItemId = ItemId(6);
+    pub const DIVE_BALL: ItemId = ItemId(7);
+    pub const NEST_BALL: ItemId = ItemId(8);
+    pub const REPEAT_BALL: ItemId = ItemId(9);
+    pub const TIMER_BALL: ItemId = ItemId(10);
+    pub const LUXURY_BALL: ItemId = ItemId(11);
+    pub const PREMIER_BALL: ItemId = ItemId(12);
+    pub const DUSK_BALL: ItemId = ItemId(13);
+    pub const HEAL_BALL: ItemId = ItemId(14);
+    pub const QUICK_BALL: ItemId = ItemId(15);
+    pub const CHERISH_BALL: ItemId = ItemId(16);
+    pub const LURE_BALL: ItemId = ItemId(449);
+    pub const LEVEL_BALL: ItemId = ItemId(450);
+    pub const MOON_BALL: ItemId = ItemId(451);
+    pub const HEAVY_BALL: ItemId = ItemId(452);
+    pub const FAST_BALL: ItemId = ItemId(453);
+    pub const FRIEND_BALL: ItemId = ItemId(454);
+    pub const LOVE_BALL: ItemId = ItemId(455);
+    pub const PARK_BALL: ItemId = ItemId(456);
+    pub const SPORT_BALL: ItemId = ItemId(457);
+    pub const DREAM_BALL: ItemId = ItemId(617);
+}
+
+/// The effect of a ball on the capture probability.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BallBonus {
+    /// The ball always catches the Pokémon.
+    Guaranteed,
+    /// The capture rate is multiplied by the value.
+    Multiplier(f64),
+}
+
+/// An invalid capture attempt.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CaptureError {
+    /// The item is not a ball usable in the generation.
+    Ball(ItemId, Generation),
+    /// The current HP is 0 or greater than the max HP.
+    HP(u16, u16),
+}
+
+impl Display for CaptureError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            CaptureError::Ball(item, generation)
+                => write!(f, "Item {} is not a ball in Generation {:?}",
+                          item.0, generation),
+            CaptureError::HP(hp, max_hp)
+                => write!(f, "Invalid HP {}/{}", hp, max_hp),
+        }
+    }
+}
+
+impl StdError for CaptureError { }
+
+/// The result of a simulated capture attempt.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Outcome {
+    /// Whether the Pokémon was caught.
+    pub caught: bool,
+    /// The number of times the ball shook, from 0 to 3. Shakes are only
+    /// simulated from Generation III onward, and are 0 before.
+    pub shakes: u8,
+}
+
+/// An attempt to catch a wild Pokémon.
+#[derive(Copy, Clone, Debug)]
+pub struct Capture {
+    /// The generation whose capture algorithm is used.
+    pub generation: Generation,
+    /// The capture rate of the Pokémon's species.
+    pub capture_rate: u8,
+    /// The Pokémon's types, for the Net Ball.
+    pub types: OneOrTwo<Type>,
+    /// The Pokémon's level, for the Nest Ball.
+    pub level: u8,
+    /// The Pokémon's max HP.
+    pub max_hp: u16,
+    /// The Pokémon's current HP.
+    pub hp: u16,
+    /// The Pokémon's non-volatile status ailment, if any.
+    pub ailment: Ailment,
+    /// The item ID of the ball used.
+    pub ball: ItemId,
+    /// The turn of the battle the ball is used, starting at 1, for the Timer
+    /// and Quick Balls.
+    pub turn: u16,
+    /// Whether the species has been caught before, for the Repeat Ball.
+    pub caught_before: bool,
+    /// Whether it is night or the battle is in a cave, for the Dusk Ball.
+    pub dark: bool,
+    /// Whether the battle is underwater, or on water in Generation IV, for the
+    /// Dive Ball.
+    pub underwater: bool,
+}
+
+impl Default for Capture {
+    fn default() -> Self {
+        Capture {
+            generation: Default::default(),
+            capture_rate: 255,
+            types: Default::default(),
+            level: 1,
+            max_hp: 1,
+            hp: 1,
+            ailment: Ailment::None,
+            ball: POKE_BALL,
+            turn: 1,
+            caught_before: false,
+            dark: false,
+            underwater: false,
+        }
+    }
+}
+
+impl Capture {
+    /// An attempt to catch a Pokémon with a ball, in Generation V by default.
+    pub fn new(
+        species: &Species, pokemon: &Pokemon, level: u8, max_hp: u16, hp: u16,
+        ball: ItemId,
+    ) -> Self {
+        Capture {
+            capture_rate: species.capture_rate,
+            types: pokemon.types,
+            level,
+            max_hp,
+            hp,
+            ball,
+            .. Default::default()
+        }
+    }
+
+    /// The effect of the ball used in the capture's generation.
+    ///
+    /// The Apricorn Balls of Generation II (and HeartGold and SoulSilver) are
+    /// accepted, but their conditional bonuses are not modeled.
+    pub fn ball_bonus(&self) -> Result<BallBonus, CaptureError> {
+        let generation = self.generation.repr() + 1;
+        let introduced = match self.ball {
+            MASTER_BALL | ULTRA_BALL | GREAT_BALL | POKE_BALL | SAFARI_BALL
+                => 1,
+            LURE_BALL | LEVEL_BALL | MOON_BALL | HEAVY_BALL | FAST_BALL
+                | FRIEND_BALL | LOVE_BALL | SPORT_BALL => 2,
+            NET_BALL | DIVE_BALL | NEST_BALL | REPEAT_BALL | TIMER_BALL
+                | LUXURY_BALL | PREMIER_BALL => 3,
+            DUSK_BALL | HEAL_BALL | QUICK_BALL | CHERISH_BALL | PARK_BALL => 4,
+            DREAM_BALL => 5,
+            _ => return Err(CaptureError::Ball(self.ball, self.generation)),
+        };
+        if introduced > generation {
+            return Err(CaptureError::Ball(self.ball, self.generation));
+        }
+        let turns = self.turn.saturating_sub(1) as f64;
+        let multiplier = match self.ball {
+            MASTER_BALL | PARK_BALL | DREAM_BALL
+                => return Ok(BallBonus::Guaranteed),
+            ULTRA_BALL => 2.0,
+            GREAT_BALL | SAFARI_BALL | SPORT_BALL => 1.5,
+            NET_BALL if self.types.contains(Type::Water)
+                || self.types.contains(Type::Bug) => 3.0,
+            DIVE_BALL if self.underwater => 3.5,
+            NEST_BALL if generation < 5
+                => ((40.0 - self.level as f64) / 10.0).max(1.0),
+            NEST_BALL => ((41.0 - self.level as f64) / 10.0).max(1.0),
+            REPEAT_BALL if self.caught_before => 3.0,
+            TIMER_BALL if generation < 5 => ((turns + 10.0) / 10.0).min(4.0),
+            TIMER_BALL => (1.0 + turns * 1229.0 / 4096.0).min(4.0),
+            DUSK_BALL if self.dark => 3.5,
+            QUICK_BALL if self.turn <= 1 && generation < 5 => 4.0,
+            QUICK_BALL if self.turn <= 1 => 5.0,
+            _ => 1.0,
+        };
+        Ok(BallBonus::Multiplier(multiplier))
+    }
+
+    /// The exact probability that the Pokémon is caught, from 0 to 1.
+    pub fn probability(&self) -> Result<f64, CaptureError> {
+        let bonus = match self.check()? {
+            BallBonus::Guaranteed => return Ok(1.0),
+            BallBonus::Multiplier(x) => x,
+        };
+        Ok(match self.generation {
+            Generation::I => {
+                let (range, factor) = self.gen1_ball();
+                let status = self.gen1_status();
+                let chance = self.gen1_chance(factor) as f64;
+                let total: f64 = (0..=range).map(|r| match r {
+                    _ if r < status => 1.0,
+                    _ if r - status > self.capture_rate as u16 => 0.0,
+                    _ => (chance + 1.0) / 256.0,
+                }).sum();
+                total / (range + 1) as f64
+            },
+            Generation::II => (self.gen2_value(bonus) as f64 + 1.0) / 256.0,
+            _ => match self.modified_rate(bonus) {
+                x if x >= 255 => 1.0,
+                x => {
+                    let (threshold, checks) = self.shake_check(x);
+                    (threshold as f64 / 65536.0).powi(checks as i32)
+                },
+            },
+        })
+    }
+
+    /// Simulate the capture attempt with a random number generator.
+    pub fn simulate<R: Rng>(
+        &self, rng: &mut R
+    ) -> Result<Outcome, CaptureError> {
+        let bonus = match self.check()? {
+            BallBonus::Guaranteed
+                => return Ok(Outcome { caught: true, shakes: 3 }),
+            BallBonus::Multiplier(x) => x,
+        };
+        let caught = match self.generation {
+            Generation::I => {
+                let (range, factor) = self.gen1_ball();
+                let status = self.gen1_status();
+                let r = rng.gen_range(0, range + 1);
+                r < status || (r - status <= self.capture_rate as u16
+                    && rng.gen_range(0, 256) <= self.gen1_chance(factor))
+            },
+            Generation::II
+                => rng.gen_range(0, 256) <= self.gen2_value(bonus),
+            _ => {
+                let rate = self.modified_rate(bonus);
+                if rate >= 255 {
+                    return Ok(Outcome { caught: true, shakes: 3 });
+                }
+                let (threshold, checks) = self.shake_check(rate);
+                let passed = (0..checks)
+                    .take_while(|_| rng.gen_range(0, 65536) < threshold)
+                    .count() as u8;
+                return Ok(Outcome {
+                    caught: passed == checks,
+                    shakes: passed.min(3),
+                });
+            },
+        };
+        Ok(Outcome { caught, shakes: 0 })
+    }
+
+    /// Simulate the capture attempt with a random number generator seeded
+    /// with `seed`, so that the outcome is reproducible.
+    pub fn simulate_seeded(&self, seed: u64) -> Result<Outcome, CaptureError> {
+        self.simulate(&mut StdRng::seed_from_u64(seed))
+    }
+
+    fn check(&self) -> Result<BallBonus, CaptureError> {
+        if self.hp == 0 || self.hp > self.max_hp {
+            return Err(CaptureError::HP(self.hp, self.max_hp));
+        }
+        self.ball_bonus()
+    }
+
+    /// The range of the first random number and the ball factor in
+    /// Generation I.
+    fn gen1_ball(&self) -> (u16, u32) {
+        match self.ball {
+            POKE_BALL => (255, 12),
+            GREAT_BALL => (200, 8),
+            _ => (150, 12),
+        }
+    }
+
+    fn gen1_status(&self) -> u16 {
+        match self.ailment {
+            Ailment::Sleep | Ailment::Freeze => 25,
+            Ailment::Paralysis | Ailment::Burn | Ailment::Poison => 12,
+            _ => 0,
+        }
+    }
+
+    /// The HP factor compared to the second random number in Generation I.
+    fn gen1_chance(&self, ball_factor: u32) -> u32 {
+        let hp = (self.hp as u32 / 4).max(1);
+        (self.max_hp as u32 * 255 / ball_factor / hp).min(255)
+    }
+
+    /// The value compared to a random byte in Generation II.
+    ///
+    /// Due to a bug, only sleep and freeze increase the chance.
+    fn gen2_value(&self, bonus: f64) -> u32 {
+        let max_hp = self.max_hp as f64;
+        let rate = ((3.0 * max_hp - 2.0 * self.hp as f64)
+            * (self.capture_rate as f64 * bonus).min(255.0)
+            / (3.0 * max_hp)).floor().max(1.0) as u32;
+        let status = match self.ailment {
+            Ailment::Sleep | Ailment::Freeze => 10,
+            _ => 0,
+        };
+        (rate + status).min(255)
+    }
+
+    /// The modified capture rate from Generation III onward.
+    fn modified_rate(&self, bonus: f64) -> u32 {
+        let max_hp = self.max_hp as f64;
+        let status = match (self.ailment, self.generation) {
+            (Ailment::Sleep, Generation::V)
+                | (Ailment::Freeze, Generation::V) => 2.5,
+            (Ailment::Sleep, _) | (Ailment::Freeze, _) => 2.0,
+            (Ailment::Paralysis, _) | (Ailment::Burn, _)
+                | (Ailment::Poison, _) => 1.5,
+            _ => 1.0,
+        };
+        ((3.0 * max_hp - 2.0 * self.hp as f64) * self.capture_rate as f64
+            * bonus / (3.0 * max_hp) * status).floor() as u32
+    }
+
+    /// The threshold for each shake check out of 65536, and the number of
+    /// checks, from Generation III onward.
+    fn shake_check(&self, rate: u32) -> (u32, u8) {
+        if rate == 0 {
+            return (0, 4);
+        }
+        match self.generation {
+            Generation::V => {
+                let root = (255.0 / rate as f64).sqrt().sqrt();
+                ((65536.0 / root).floor() as u32, 3)
+            },
+            _ => {
+                let root = ((16_711_680 / rate) as f64).sqrt().floor()
+                    .sqrt().floor();
+                ((1_048_560.0 / root).floor() as u32, 4)
+            },
+        }
+    }
+}
@@ -0,0 +1,536 @@
+//! Damage dealt by moves in battle.
+//!
+//! `damage` implements the formula of Generation V, taking into account
+//! same-type attack bonus (STAB), type effectiveness, critical hits, burns,
+//! stat stages, spread moves, weather, and type-boosting held items. Other
+//! abilities and held items are not modeled, but most can be applied to the
+//! stats or to the power of the move.
+//!
+//! Each earlier generation orders and rounds the modifiers differently, and
+//! before Generation IV a move is physical or special based on its type (see
+//! `type_damage_class`). `damage_for` selects the formula by generation.
+
+use crate::{Efficacy, EfficacyTable, Enum, Stat, Type};
+use crate::items::ItemId;
+use crate::moves::{DamageClass, Move};
+use crate::pokemon::OneOrTwo;
+use crate::stats::Spread;
+use crate::versions::Generation;
+
+/// The lowest stat stage.
+pub const MIN_STAGE: i8 = -6;
+
+/// The highest stat stage.
+pub const MAX_STAGE: i8 = 6;
+
+/// The held items that boost the damage of moves of a type, with that type.
+pub const TYPE_BOOSTING_ITEMS: [(ItemId, Type); 17] = [
+    (ItemId(199), Type::Bug),
+    (ItemId(210), Type::Steel),
+    (ItemId(214), Type::Ground),
+    (ItemId(215), Type::Rock),
+    (ItemId(216), Type::Grass),
+    (ItemId(217), Type::Dark),
+    (ItemId(218), Type::Fighting),
+    (ItemId(219), Type::Electric),
+    (ItemId(220), Type::Water),
+    (ItemId(221), Type::Flying),
+    (ItemId(222), Type::Poison),
+    (ItemId(223), Type::Ice),
+    (ItemId(224), Type::Ghost),
+    (ItemId(225), Type::Psychic),
+    (ItemId(226), Type::Fire),
+    (ItemId(227), Type::Dragon),
+    (ItemId(228), Type::Normal),
+];
+
+/// The weather in battle.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Weather {
+    Clear,
+    /// Boosts Water-type moves and weakens Fire-type moves.
+    Rain,
+    /// Boosts Fire-type moves and weakens Water-type moves.
+    Sun,
+    /// Boosts the Special Defense of Rock-type Pokémon from Generation IV
+    /// onward.
+    Sandstorm,
+    Hail,
+}
+
+impl Default for Weather {
+    fn default() -> Self { Weather::Clear }
+}
+
+/// A Pokémon in battle, as it affects the damage it deals or takes.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Battler {
+    pub level: u8,
+    pub types: OneOrTwo<Type>,
+    /// The Pokémon's stats, e.g. from `stats::calculate`.
+    pub stats: Spread<u16>,
+    /// The Pokémon's stat stages, from `MIN_STAGE` to `MAX_STAGE`. The HP
+    /// stage is ignored.
+    pub stages: Spread<i8>,
+    /// Whether the Pokémon is burned, halving the damage of its physical
+    /// moves.
+    pub burned: bool,
+    /// The Pokémon's held item. Only `TYPE_BOOSTING_ITEMS` are modeled.
+    pub held_item: Option<ItemId>,
+}
+
+/// The circumstances in which a move is used.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Circumstances {
+    /// The power of the move, if not `Move.power`, as for moves whose power
+    /// varies (see e.g. `weight::move_power`).
+    pub power: Option<u8>,
+    /// Whether the move is a critical hit.
+    pub critical: bool,
+    /// Whether the move hits more than one target.
+    pub spread: bool,
+    pub weather: Weather,
+}
+
+/// The possible damage of a move.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Damage {
+    /// The damage for each equally likely random factor, from lowest to
+    /// highest. There are 39 in Generations I and II, and 16 from Generation
+    /// III onward.
+    pub rolls: Vec<u16>,
+}
+
+impl Damage {
+    /// The lowest possible damage.
+    pub fn min(&self) -> u16 {
+        self.rolls.first().cloned().unwrap_or(0)
+    }
+
+    /// The highest possible damage.
+    pub fn max(&self) -> u16 {
+        self.rolls.last().cloned().unwrap_or(0)
+    }
+
+    /// The probability of knocking out a target with the given HP in one hit.
+    pub fn ko_chance(&self, hp: u16) -> f64 {
+        self.ko_chance_in(1, hp)
+    }
+
+    /// The probability of knocking out a target with the given HP within the
+    /// given number of hits, each with an independent random factor.
+    pub fn ko_chance_in(&self, hits: u8, hp: u16) -> f64 {
+        if hp == 0 {
+            return 1.0;
+        }
+        if self.rolls.is_empty() {
+            return 0.0;
+        }
+        let roll_chance = 1.0 / self.rolls.len() as f64;
+        // The chance of each amount of HP remaining, with 0 for a knockout.
+        let mut remaining = vec![0.0; hp as usize + 1];
+        remaining[hp as usize] = 1.0;
+        for _ in 0..hits {
+            let mut next = vec![0.0; hp as usize + 1];
+            next[0] = remaining[0];
+            for (left, &chance) in remaining.iter().enumerate().skip(1) {
+                if chance == 0.0 {
+                    continue;
+                }
+                for &roll in &self.rolls {
+                    next[left.saturating_sub(roll as usize)]
+                        += chance * roll_chance;
+                }
+            }
+            remaining = next;
+        }
+        remaining[0]
+    }
+}
+
+/// A stat modified by a stat stage.
+pub fn apply_stage(stat: u16, stage: i8) -> u16 {
+    let stage = stage.clamp(MIN_STAGE, MAX_STAGE);
+    let (numerator, denominator) = if stage >= 0 {
+        (2 + stage as u32, 2)
+    } else {
+        (2, 2 + stage.unsigned_abs() as u32)
+    };
+    (stat as u32 * numerator / denominator).min(u16::MAX as u32) as u16
+}
+
+/// The type boosted by a held item, if any.
+pub fn boosted_type(item: ItemId) -> Option<Type> {
+    TYPE_BOOSTING_ITEMS.iter().find(|&&(i, _)| i == item).map(|&(_, t)| t)
+}
+
+/// The damage class of damaging moves of a type before Generation IV.
+///
+/// Normal, Fighting, Flying, Poison, Ground, Rock, Bug, Ghost, and Steel
+/// moves are physical, and the rest are special.
+pub fn type_damage_class(typ: Type) -> DamageClass {
+    if typ.repr() < Type::Fire.repr() {
+        DamageClass::Physical
+    } else {
+        DamageClass::Special
+    }
+}
+
+/// The chance of a critical hit from Generation III to V at the given critical
+/// hit stage, e.g. `Meta.critical_rate` for a move with a high critical hit
+/// ratio. Generation II differs only at stage 0, with a chance of 17/256.
+pub fn critical_chance(stage: i8) -> f64 {
+    match stage {
+        i8::MIN..=0 => 1.0 / 16.0,
+        1 => 1.0 / 8.0,
+        2 => 1.0 / 4.0,
+        3 => 1.0 / 3.0,
+        _ => 1.0 / 2.0,
+    }
+}
+
+/// The chance of a critical hit in Generation I, which depends on the base
+/// Speed of the user and whether the move has a high critical hit ratio.
+pub fn critical_chance_by_speed(base_speed: u8, high_ratio: bool) -> f64 {
+    let mut threshold = base_speed as u32 / 2;
+    if high_ratio {
+        threshold = (threshold * 8).min(255);
+    }
+    threshold as f64 / 256.0
+}
+
+/// The efficacy of a move's type against each of the target's types.
+fn efficacies(
+    efficacy: &EfficacyTable, typ: Type, target: OneOrTwo<Type>
+) -> Vec<Efficacy> {
+    std::iter::once(target.first()).chain(target.second())
+        .map(|t| efficacy[(typ, t)])
+        .collect()
+}
+
+/// Apply each efficacy in turn, rounding down.
+fn apply_efficacies(damage: u32, efficacies: &[Efficacy]) -> u32 {
+    efficacies.iter().fold(damage, |damage, &e| match e {
+        Efficacy::Super => damage * 2,
+        Efficacy::NotVery => damage / 2,
+        _ => damage,
+    })
+}
+
+/// The attacking and defending stats used by a move in the generation, or
+/// `None` if it does not deal damage.
+fn attacking_stats(
+    generation: Generation, move_: &Move
+) -> Option<(Stat, Stat)> {
+    let damage_class = match move_.damage_class {
+        DamageClass::NonDamaging => return None,
+        _ if generation.repr() < Generation::IV.repr()
+            => type_damage_class(move_.type_in(generation)),
+        damage_class => damage_class,
+    };
+    match damage_class {
+        DamageClass::Physical => Some((Stat::Attack, Stat::Defense)),
+        _ => Some((Stat::SpecialAttack, Stat::SpecialDefense)),
+    }
+}
+
+/// Whether the user's held item boosts the move.
+fn is_boosted(user: &Battler, move_: &Move) -> bool {
+    user.held_item.and_then(boosted_type) == Some(move_.typ)
+}
+
+/// The power of the move in the circumstances.
+fn power(move_: &Move, circumstances: &Circumstances) -> u32 {
+    circumstances.power.unwrap_or(move_.power) as u32
+}
+
+/// The attacking and defending stats with stat stages applied, from
+/// Generation III onward. A critical hit ignores the user's negative attacking
+/// stage and the target's positive defending stage.
+fn staged_stats(
+    user: &Battler, target: &Battler, attack: u16, stats: (Stat, Stat),
+    critical: bool,
+) -> (u32, u32) {
+    let mut attack_stage = user.stages[stats.0];
+    let mut defense_stage = target.stages[stats.1];
+    if critical {
+        attack_stage = attack_stage.max(0);
+        defense_stage = defense_stage.min(0);
+    }
+    (apply_stage(attack, attack_stage) as u32,
+     apply_stage(target.stats[stats.1], defense_stage).max(1) as u32)
+}
+
+/// Apply a Generation V modifier out of 4096, rounding halves down.
+///
+/// The product is computed in 64 bits, and the result saturates.
+fn apply_modifier(value: u32, modifier: u32) -> u32 {
+    let result = (value as u64 * modifier as u64 + 2047) / 4096;
+    result.min(u32::MAX as u64) as u32
+}
+
+/// The Generation V weather modifier for a move of the given type.
+fn weather_modifier(weather: Weather, typ: Type) -> u32 {
+    match (weather, typ) {
+        (Weather::Rain, Type::Water) | (Weather::Sun, Type::Fire) => 6144,
+        (Weather::Rain, Type::Fire) | (Weather::Sun, Type::Water) => 2048,
+        _ => 4096,
+    }
+}
+
+/// Apply the weather modifier of Generations II to IV, rounding down.
+fn apply_weather(damage: u32, weather: Weather, typ: Type) -> u32 {
+    match weather_modifier(weather, typ) {
+        6144 => damage * 3 / 2,
+        2048 => damage / 2,
+        _ => damage,
+    }
+}
+
+/// The damage dealt by the user's move to the target in the generation, or
+/// `None` if the move does not deal damage.
+///
+/// The efficacy table should be that of the generation (see
+/// `EfficacyTable::for_generation`), and the types of the Pokémon those they
+/// had in the generation (see `Pokemon::types_in`).
+pub fn damage_for(
+    generation: Generation, efficacy: &EfficacyTable, move_: &Move,
+    user: &Battler, target: &Battler, circumstances: &Circumstances,
+) -> Option<Damage> {
+    let calculate = match generation {
+        Generation::I => damage_gen_i,
+        Generation::II => damage_gen_ii,
+        Generation::III => damage_gen_iii,
+        Generation::IV => damage_gen_iv,
+        Generation::V => damage,
+    };
+    calculate(efficacy, move_, user, target, circumstances)
+}
+
+/// The attacking and defending stats of Generations I and II, which are both
+/// divided by 4 if either exceeds 255.
+fn scale_retro_stats(attack: u32, defense: u32) -> (u32, u32) {
+    if attack > 255 || defense > 255 {
+        (attack / 4, (defense / 4).max(1))
+    } else {
+        (attack, defense.max(1))
+    }
+}
+
+/// The random rolls of Generations I and II from 217/255 to 255/255, except
+/// that damage of 1 is not randomized.
+fn retro_rolls(damage: u32, immune: bool) -> Damage {
+    let rolls = (217..=255).map(|random| match damage {
+        _ if immune => 0,
+        1 => 1,
+        _ => (damage * random / 255).min(u16::MAX as u32) as u16,
+    }).collect();
+    Damage { rolls }
+}
+
+/// The damage in Generation I, with the move's type in Generation I.
+///
+/// A critical hit doubles the user's level in the formula, and ignores stat
+/// stages and burns.
+fn damage_gen_i(
+    efficacy: &EfficacyTable, move_: &Move, user: &Battler, target: &Battler,
+    circumstances: &Circumstances,
+) -> Option<Damage> {
+    let stats = attacking_stats(Generation::I, move_)?;
+    let c = circumstances;
+    let (attack, defense) = if c.critical {
+        (user.stats[stats.0], target.stats[stats.1])
+    } else {
+        let mut attack = apply_stage(user.stats[stats.0], user.stages[stats.0]);
+        if user.burned && stats.0 == Stat::Attack {
+            attack /= 2;
+        }
+        (attack, apply_stage(target.stats[stats.1], target.stages[stats.1]))
+    };
+    let (attack, defense) = scale_retro_stats(attack as u32, defense as u32);
+    let level = user.level as u32 * if c.critical { 2 } else { 1 };
+    let mut damage = ((2 * level / 5 + 2) * power(move_, c) * attack
+        / defense / 50).min(997) + 2;
+    let typ = move_.type_in(Generation::I);
+    if user.types.contains(typ) {
+        damage += damage / 2;
+    }
+    let efficacies = efficacies(efficacy, typ, target.types);
+    damage = apply_efficacies(damage, &efficacies);
+    Some(retro_rolls(damage, efficacies.contains(&Efficacy::Not)))
+}
+
+/// The damage in Generation II.
+///
+/// A critical hit doubles the damage, and ignores stat stages and burns if
+/// the target's defending stage is at least the user's attacking stage.
+fn damage_gen_ii(
+    efficacy: &EfficacyTable, move_: &Move, user: &Battler, target: &Battler,
+    circumstances: &Circumstances,
+) -> Option<Damage> {
+    let stats = attacking_stats(Generation::II, move_)?;
+    let c = circumstances;
+    let attack_stage = user.stages[stats.0];
+    let defense_stage = target.stages[stats.1];
+    let (attack, defense) = if c.critical && defense_stage >= attack_stage {
+        (user.stats[stats.0], target.stats[stats.1])
+    } else {
+        let mut attack = apply_stage(user.stats[stats.0], attack_stage);
+        if user.burned && stats.0 == Stat::Attack {
+            attack /= 2;
+        }
+        (attack, apply_stage(target.stats[stats.1], defense_stage))
+    };
+    let (attack, defense) = scale_retro_stats(attack as u32, defense as u32);
+    let mut damage = (2 * user.level as u32 / 5 + 2) * power(move_, c)
+        * attack / defense / 50;
+    if c.critical {
+        damage *= 2;
+    }
+    if is_boosted(user, move_) {
+        damage = damage * 110 / 100;
+    }
+    damage = damage.min(997) + 2;
+    damage = apply_weather(damage, c.weather, move_.typ);
+    if user.types.contains(move_.typ) {
+        damage += damage / 2;
+    }
+    let efficacies = efficacies(efficacy, move_.typ, target.types);
+    damage = apply_efficacies(damage, &efficacies);
+    Some(retro_rolls(damage, efficacies.contains(&Efficacy::Not)))
+}
+
+/// The random rolls from Generation III onward from 85% to 100%, with each
+/// roll passed through the remaining modifiers.
+fn modern_rolls<F: Fn(u32) -> u32>(
+    damage: u32, immune: bool, modifiers: F
+) -> Damage {
+    let rolls = (85..=100).map(|random| if immune { 0 } else {
+        modifiers(damage * random / 100).clamp(1, u16::MAX as u32) as u16
+    }).collect();
+    Damage { rolls }
+}
+
+/// The damage in Generation III.
+///
+/// The random factor is applied last, after STAB and type effectiveness. A
+/// spread move has its damage halved.
+fn damage_gen_iii(
+    efficacy: &EfficacyTable, move_: &Move, user: &Battler, target: &Battler,
+    circumstances: &Circumstances,
+) -> Option<Damage> {
+    let stats = attacking_stats(Generation::III, move_)?;
+    let c = circumstances;
+    let mut attack = user.stats[stats.0];
+    if is_boosted(user, move_) {
+        attack = (attack as u32 * 110 / 100) as u16;
+    }
+    let (attack, defense)
+        = staged_stats(user, target, attack, stats, c.critical);
+    let mut damage = (2 * user.level as u32 / 5 + 2) * power(move_, c)
+        * attack / defense / 50;
+    if stats.0 == Stat::Attack {
+        if user.burned {
+            damage /= 2;
+        }
+        if c.spread {
+            damage /= 2;
+        }
+        damage = damage.max(1);
+    } else {
+        if c.spread {
+            damage /= 2;
+        }
+        damage = apply_weather(damage, c.weather, move_.typ);
+    }
+    damage += 2;
+    if c.critical {
+        damage *= 2;
+    }
+    if user.types.contains(move_.typ) {
+        damage = damage * 15 / 10;
+    }
+    let efficacies = efficacies(efficacy, move_.typ, target.types);
+    damage = apply_efficacies(damage, &efficacies);
+    Some(modern_rolls(damage, efficacies.contains(&Efficacy::Not), |d| d))
+}
+
+/// The damage in Generation IV.
+///
+/// The user's attacking stat is divided by 50 before the target's defending
+/// stat. A spread move does 3/4 damage.
+fn damage_gen_iv(
+    efficacy: &EfficacyTable, move_: &Move, user: &Battler, target: &Battler,
+    circumstances: &Circumstances,
+) -> Option<Damage> {
+    let stats = attacking_stats(Generation::IV, move_)?;
+    let c = circumstances;
+    let mut power = power(move_, c);
+    if is_boosted(user, move_) {
+        power = power * 12 / 10;
+    }
+    let (attack, mut defense)
+        = staged_stats(user, target, user.stats[stats.0], stats, c.critical);
+    if c.weather == Weather::Sandstorm && stats.1 == Stat::SpecialDefense
+        && target.types.contains(Type::Rock) {
+        defense = defense * 3 / 2;
+    }
+    let mut damage = (2 * user.level as u32 / 5 + 2) * power * attack / 50
+        / defense;
+    if user.burned && stats.0 == Stat::Attack {
+        damage /= 2;
+    }
+    if c.spread {
+        damage = damage * 3 / 4;
+    }
+    damage = apply_weather(damage, c.weather, move_.typ) + 2;
+    if c.critical {
+        damage *= 2;
+    }
+    let stab = user.types.contains(move_.typ);
+    let efficacies = efficacies(efficacy, move_.typ, target.types);
+    Some(modern_rolls(damage, efficacies.contains(&Efficacy::Not), |damage| {
+        let damage = if stab { damage * 3 / 2 } else { damage };
+        apply_efficacies(damage, &efficacies)
+    }))
+}
+
+/// The damage dealt by the user's move to the target in Generation V, or
+/// `None` if the move does not deal damage.
+///
+/// A critical hit ignores the user's negative attacking stage and the
+/// target's positive defending stage.
+pub fn damage(
+    efficacy: &EfficacyTable, move_: &Move, user: &Battler, target: &Battler,
+    circumstances: &Circumstances,
+) -> Option<Damage> {
+    let stats = attacking_stats(Generation::V, move_)?;
+    let c = circumstances;
+    let mut power = power(move_, c);
+    if is_boosted(user, move_) {
+        power = apply_modifier(power, 4915);
+    }
+    let (attack, mut defense)
+        = staged_stats(user, target, user.stats[stats.0], stats, c.critical);
+    if c.weather == Weather::Sandstorm && stats.1 == Stat::SpecialDefense
+        && target.types.contains(Type::Rock) {
+        defense = defense * 3 / 2;
+    }
+    let mut base = (2 * user.level as u32 / 5 + 2) * power * attack / defense
+        / 50 + 2;
+    if c.spread {
+        base = apply_modifier(base, 3072);
+    }
+    base = apply_modifier(base, weather_modifier(c.weather, move_.typ));
+    if c.critical {
+        base *= 2;
+    }
+    let stab = user.types.contains(move_.typ);
+    let burned = user.burned && stats.0 == Stat::Attack;
+    let efficacies = efficacies(efficacy, move_.typ, target.types);
+    Some(modern_rolls(base, efficacies.contains(&Efficacy::Not), |damage| {
+        let damage = if stab { apply_modifier(damage, 6144) } else { damage };
+        let damage = apply_efficacies(damage, &efficacies);
+        if burned { damage / 2 } else { damage }
+    }))
+}
@@ -190,6 +190,11 @@
 //! }
 //! ```
 
+use std::error::Error as StdError;
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+use veekun::names_match;
+
 pub use enum_repr::EnumRepr;
 
 /// All of the vdex C-style enums implement this trait, which allows for easy
@@ -210,4 +215,44 @@ pub trait Enum where Self: 'static + Sized + Copy {
     /// Returns the enum value corresponding to the passed representation, or
     /// `None` if no such enum value exists.
     fn from_repr(x: Self::Repr) -> Option<Self>;
+
+    /// Returns the enum value whose variant name matches `name`, ignoring case
+    /// and punctuation (see `veekun::names_match`). Veekun identifiers
+    /// (`"special-attack"`), variant names (`"SpecialAttack"`), and in-game
+    /// names (`"Special Attack"`) are all accepted.
+    ///
+    /// To use `str::parse` instead, parse into `ByName`.
+    fn from_name(name: &str) -> Result<Self, ParseEnumError> where Self: Debug {
+        Self::VALUES.iter().find(|v| names_match(&format!("{:?}", v), name))
+            .cloned().ok_or_else(|| ParseEnumError(name.to_string()))
+    }
+}
+
+/// Error returned when parsing a name that does not match any enum value.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseEnumError(pub String);
+
+impl Display for ParseEnumError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "Unknown name: {}", self.0)
+    }
+}
+
+impl StdError for ParseEnumError { }
+
+/// Wrapper that parses an enum value from its name with `Enum::from_name`,
+/// e.g. `"fire".parse::<ByName<Type>>()`.
+///
+/// The enums cannot implement `FromStr` themselves, because `FromStr` types
+/// are parsed from the Veekun CSV files as they are (see `veekun::FromVeekun`)
+/// rather than from their Veekun IDs.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ByName<E>(pub E);
+
+impl<E: Enum + Debug> FromStr for ByName<E> {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, ParseEnumError> {
+        E::from_name(s).map(ByName)
+    }
 }
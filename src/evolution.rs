@@ -0,0 +1,271 @@
+//! Evolution chains, and the conditions under which Pokémon evolve.
+//!
+//! > [*[From Bulbapedia:]*](https://bulbapedia.bulbagarden.net/wiki/Evolution)
+//! > Evolution (Japanese: 進化 evolution) is a process in which a Pokémon
+//! > changes into a different species of Pokémon.
+//!
+//! `Species.evolves_from` only links each species to its pre-evolution. An
+//! `EvolutionGraph` adds the forward links and groups species into chains.
+
+use std::collections::HashMap;
+use crate::items::ItemId;
+use crate::moves::MoveId;
+use crate::pokemon::{
+    EvolutionTrigger, EvolvesFrom, Gender, SpeciesId, SpeciesTable, TimeOfDay,
+};
+
+/// The evolution relationships between all species.
+#[derive(Clone, Debug, Default)]
+pub struct EvolutionGraph {
+    from: HashMap<SpeciesId, SpeciesId>,
+    into: HashMap<SpeciesId, Vec<SpeciesId>>,
+    chains: HashMap<u16, Vec<SpeciesId>>,
+    chain_ids: HashMap<SpeciesId, u16>,
+}
+
+impl EvolutionGraph {
+    /// Build the graph from the evolution data of a species table.
+    pub fn new(species: &SpeciesTable) -> Self {
+        let mut graph = EvolutionGraph::default();
+        let mut ordered: Vec<_> = species.iter().collect();
+        ordered.sort_by_key(|s| s.order);
+        for s in ordered {
+            if let Some(from_id) = s.pre_evolution() {
+                graph.from.insert(s.id, from_id);
+                graph.into.entry(from_id).or_default().push(s.id);
+            }
+            graph.chains.entry(s.evolution_chain).or_default().push(s.id);
+            graph.chain_ids.insert(s.id, s.evolution_chain);
+        }
+        graph
+    }
+
+    /// The species a species evolves from, if any.
+    pub fn evolves_from(&self, id: SpeciesId) -> Option<SpeciesId> {
+        self.from.get(&id).cloned()
+    }
+
+    /// The species a species can evolve into directly.
+    pub fn evolves_into(&self, id: SpeciesId) -> &[SpeciesId] {
+        self.into.get(&id).map_or(&[], |v| v.as_slice())
+    }
+
+    /// The first stage of the species' evolution chain.
+    pub fn root(&self, id: SpeciesId) -> SpeciesId {
+        let mut root = id;
+        while let Some(from_id) = self.evolves_from(root) {
+            root = from_id;
+        }
+        root
+    }
+
+    /// The stage of the species in its chain, starting at 0 for the root.
+    pub fn stage(&self, id: SpeciesId) -> usize {
+        let mut stage = 0;
+        let mut current = id;
+        while let Some(from_id) = self.evolves_from(current) {
+            current = from_id;
+            stage += 1;
+        }
+        stage
+    }
+
+    /// All species in the species' evolution chain, in Pokédex sort order.
+    pub fn chain(&self, id: SpeciesId) -> &[SpeciesId] {
+        self.chain_ids.get(&id).and_then(|c| self.chains.get(c))
+            .map_or(&[], |v| v.as_slice())
+    }
+
+    /// The species in the species' evolution chain, grouped by stage.
+    pub fn stages(&self, id: SpeciesId) -> Vec<Vec<SpeciesId>> {
+        let mut stages = Vec::new();
+        let mut current = vec![self.root(id)];
+        while !current.is_empty() {
+            let next = current.iter()
+                .flat_map(|&s| self.evolves_into(s).iter().cloned())
+                .collect();
+            stages.push(current);
+            current = next;
+        }
+        stages
+    }
+
+    /// Whether any species in the species' evolution chain can evolve into
+    /// more than one species, as with Eevee, Tyrogue, and Wurmple.
+    pub fn is_branching(&self, id: SpeciesId) -> bool {
+        self.chain(id).iter().any(|&s| self.evolves_into(s).len() > 1)
+    }
+}
+
+/// The circumstances of a Pokémon at the moment it might evolve.
+#[derive(Clone, Debug, Default)]
+pub struct Conditions {
+    /// What is happening to the Pokémon: leveling up, being traded, having an
+    /// item used on it, or, for Shedinja, its pre-evolution evolving.
+    pub trigger: EvolutionTrigger,
+    pub level: u8,
+    pub gender: Gender,
+    /// The item used on the Pokémon, for `EvolutionTrigger::UseItem`.
+    pub used_item: Option<ItemId>,
+    pub held_item: Option<ItemId>,
+    pub happiness: u8,
+    pub beauty: u8,
+    pub time_of_day: TimeOfDay,
+    /// The Veekun ID of the current location.
+    pub location: Option<u16>,
+    pub known_moves: Vec<MoveId>,
+    pub attack: u16,
+    pub defense: u16,
+    /// The species of the other Pokémon in the party.
+    pub party: Vec<SpeciesId>,
+    /// The species the Pokémon is being traded for, if being traded.
+    pub traded_for: Option<SpeciesId>,
+}
+
+impl EvolvesFrom {
+    /// Whether the conditions satisfy all the requirements of this evolution.
+    pub fn is_satisfied(&self, conditions: &Conditions) -> bool {
+        let c = conditions;
+        let relative_stats = (c.attack as i32 - c.defense as i32).signum();
+        self.trigger == c.trigger
+            && c.level >= self.level
+            && (self.gender == Gender::Genderless || self.gender == c.gender)
+            && self.trigger_item.is_none_or(|i| c.used_item == Some(i))
+            && self.held_item.is_none_or(|i| c.held_item == Some(i))
+            && self.location.is_none_or(|l| c.location == Some(l))
+            && self.time_of_day.is_none_or(|t| c.time_of_day == t)
+            && self.move_id.is_none_or(|m| c.known_moves.contains(&m))
+            && self.min_happiness.is_none_or(|h| c.happiness >= h)
+            && self.min_beauty.is_none_or(|b| c.beauty >= b)
+            && self.relative_physical_stats
+                .is_none_or(|r| relative_stats == r as i32)
+            && self.party_species.is_none_or(|s| c.party.contains(&s))
+            && self.trade_species.is_none_or(|s| c.traded_for == Some(s))
+    }
+}
+
+/// The species a species can evolve into under the given conditions.
+pub fn possible_evolutions(
+    species: &SpeciesTable, graph: &EvolutionGraph, id: SpeciesId,
+    conditions: &Conditions,
+) -> Vec<SpeciesId> {
+    graph.evolves_into(id).iter().cloned()
+        .filter(|&into| species[into].evolves_from.iter()
+            .any(|e| e.is_satisfied(conditions)))
+        .collect()
+}
+
+/// A Pokémon owned by a trainer, with the state that affects its evolution.
+#[derive(Clone, Debug, Default)]
+pub struct OwnedPokemon {
+    pub species: SpeciesId,
+    pub level: u8,
+    pub gender: Gender,
+    pub held_item: Option<ItemId>,
+    pub happiness: u8,
+    pub beauty: u8,
+    pub known_moves: Vec<MoveId>,
+    pub attack: u16,
+    pub defense: u16,
+}
+
+/// Something that happens to a Pokémon that may cause it to evolve.
+///
+/// There is no event for `EvolutionTrigger::Shed`: it happens alongside a
+/// level-up evolution (see `Evolution.shed`).
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Event {
+    /// The Pokémon leveled up.
+    LevelUp,
+    /// The Pokémon was traded, for a Pokémon of the given species if known.
+    Trade(Option<SpeciesId>),
+    /// The item was used on the Pokémon.
+    UseItem(ItemId),
+}
+
+/// The surroundings of a Pokémon that may evolve.
+#[derive(Clone, Debug, Default)]
+pub struct Environment {
+    pub time_of_day: TimeOfDay,
+    /// The Veekun ID of the current location.
+    pub location: Option<u16>,
+    /// The species of the other Pokémon in the party.
+    pub party: Vec<SpeciesId>,
+    /// Whether there is a Poké Ball in the bag. This is only required for
+    /// shedding from Generation IV onward, so should be `true` for earlier
+    /// games.
+    pub poke_ball: bool,
+}
+
+/// The result of a Pokémon evolving.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Evolution {
+    /// The species the Pokémon evolved into.
+    pub species: SpeciesId,
+    /// The species of a new Pokémon added to the party, as Shedinja is when
+    /// Nincada evolves.
+    pub shed: Option<SpeciesId>,
+    /// Whether the held item was used up by the evolution.
+    pub consumes_held_item: bool,
+}
+
+impl OwnedPokemon {
+    /// The conditions for the Pokémon when the event happens.
+    pub fn conditions(
+        &self, event: &Event, environment: &Environment
+    ) -> Conditions {
+        let (trigger, used_item, traded_for) = match *event {
+            Event::LevelUp => (EvolutionTrigger::LevelUp, None, None),
+            Event::Trade(with) => (EvolutionTrigger::Trade, None, with),
+            Event::UseItem(item)
+                => (EvolutionTrigger::UseItem, Some(item), None),
+        };
+        Conditions {
+            trigger,
+            level: self.level,
+            gender: self.gender,
+            used_item,
+            held_item: self.held_item,
+            happiness: self.happiness,
+            beauty: self.beauty,
+            time_of_day: environment.time_of_day,
+            location: environment.location,
+            known_moves: self.known_moves.clone(),
+            attack: self.attack,
+            defense: self.defense,
+            party: environment.party.clone(),
+            traded_for,
+        }
+    }
+}
+
+/// The evolution that happens to the Pokémon when the event happens, if any.
+///
+/// If more than one evolution is possible, the first in Pokédex order is
+/// chosen. A species that sheds is only added if there is space in the party
+/// and a Poké Ball in the bag.
+pub fn evolve(
+    species: &SpeciesTable, graph: &EvolutionGraph, pokemon: &OwnedPokemon,
+    event: &Event, environment: &Environment,
+) -> Option<Evolution> {
+    let conditions = pokemon.conditions(event, environment);
+    let into = graph.evolves_into(pokemon.species);
+    let (into_id, method) = into.iter().find_map(|&into_id| {
+        species[into_id].evolves_from.iter()
+            .find(|e| e.is_satisfied(&conditions))
+            .map(|e| (into_id, e))
+    })?;
+    let can_shed = method.trigger == EvolutionTrigger::LevelUp
+        && environment.party.len() < 5 && environment.poke_ball;
+    let shed = if can_shed {
+        into.iter().cloned().find(|&shed_id| species[shed_id].evolves_from
+            .iter().any(|e| e.trigger == EvolutionTrigger::Shed))
+    } else {
+        None
+    };
+    Some(Evolution {
+        species: into_id,
+        shed,
+        consumes_held_item: method.held_item.is_some(),
+    })
+}
@@ -0,0 +1,127 @@
+//! Experience gained from knocking out a Pokémon.
+//!
+//! > [*[From Bulbapedia:]*](https://bulbapedia.bulbagarden.net/wiki/Experience)
+//! > Experience (Japanese: 経験値 experience points), often shortened to Exp.,
+//! > is a game mechanic which affects the growth and development of a
+//! > Pokémon. . . . Experience is gained by defeating Pokémon in battle.
+//!
+//! See `pokemon::GrowthRate` for the experience needed for each level.
+
+use crate::Enum;
+use crate::versions::Generation;
+
+/// Whether the Pokémon gaining experience was obtained in a trade.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Trade {
+    /// The Pokémon is with its original trainer.
+    None,
+    /// The Pokémon was traded from a game of the same language, giving a 1.5
+    /// times bonus.
+    Domestic,
+    /// The Pokémon was traded from a game of a different language, giving a
+    /// 1.7 times bonus from Generation IV onward (1.5 before).
+    International,
+}
+
+impl Default for Trade {
+    fn default() -> Self { Trade::None }
+}
+
+/// The circumstances of a knockout that determine the experience gained.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Knockout {
+    /// The base experience of the fainted Pokémon (`Pokemon.base_experience`).
+    pub base_experience: u16,
+    /// The level of the fainted Pokémon.
+    pub fainted_level: u8,
+    /// The level of the Pokémon gaining experience.
+    pub winner_level: u8,
+    /// The number of Pokémon that participated in the battle and did not
+    /// faint, among which the experience is split.
+    pub participants: u8,
+    /// Whether the fainted Pokémon belonged to a trainer, giving a 1.5 times
+    /// bonus.
+    pub trainer_owned: bool,
+    /// Whether the Pokémon gaining experience was traded.
+    pub trade: Trade,
+    /// Whether the Pokémon gaining experience holds a Lucky Egg, giving a 1.5
+    /// times bonus.
+    pub lucky_egg: bool,
+}
+
+impl Default for Knockout {
+    fn default() -> Self {
+        Knockout {
+            base_experience: 0,
+            fainted_level: 1,
+            winner_level: 1,
+            participants: 1,
+            trainer_owned: false,
+            trade: Trade::None,
+            lucky_egg: false,
+        }
+    }
+}
+
+impl Knockout {
+    /// The experience gained with the formula of the given generation.
+    ///
+    /// Generation V scales the experience by the difference in level between
+    /// the two Pokémon (see `scaled_experience`), while earlier generations
+    /// do not (see `flat_experience`).
+    pub fn experience(&self, generation: Generation) -> u32 {
+        match generation {
+            Generation::V => self.scaled_experience(),
+            _ => self.flat_experience(generation),
+        }
+    }
+
+    /// The experience gained in Generations I to IV:
+    /// `b * L / 7 / s`, with each bonus applied in turn.
+    pub fn flat_experience(&self, generation: Generation) -> u32 {
+        let mut experience = self.base_experience as u32
+            * self.fainted_level as u32 / 7;
+        experience /= self.participants.max(1) as u32;
+        if self.trainer_owned {
+            experience = experience * 3 / 2;
+        }
+        experience = match self.trade {
+            Trade::None => experience,
+            Trade::International
+                if generation.repr() >= Generation::IV.repr()
+                => experience * 17 / 10,
+            _ => experience * 3 / 2,
+        };
+        if self.lucky_egg {
+            experience = experience * 3 / 2;
+        }
+        experience
+    }
+
+    /// The experience gained in Generation V:
+    /// `(b * L / (5 * s) * ((2L + 10) / (L + Lp + 10))^2.5 + 1)`, with the
+    /// bonuses applied to the result.
+    ///
+    /// This is calculated in floating point, so may differ from the games by
+    /// a point due to rounding.
+    pub fn scaled_experience(&self) -> u32 {
+        let level = self.fainted_level as f64;
+        let winner = self.winner_level as f64;
+        let mut experience = self.base_experience as f64 * level
+            / (5.0 * self.participants.max(1) as f64);
+        if self.trainer_owned {
+            experience *= 1.5;
+        }
+        let scale = (2.0 * level + 10.0) / (level + winner + 10.0);
+        let mut experience = (experience * scale.powf(2.5)).floor() + 1.0;
+        experience *= match self.trade {
+            Trade::None => 1.0,
+            Trade::Domestic => 1.5,
+            Trade::International => 1.7,
+        };
+        if self.lucky_egg {
+            experience *= 1.5;
+        }
+        experience as u32
+    }
+}
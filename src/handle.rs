@@ -0,0 +1,59 @@
+use std::sync::{Arc, RwLock};
+use crate::DataSource;
+use crate::LoadError;
+use crate::Pokedex;
+
+/// A shareable reference to a Pokedex which can be swapped atomically.
+///
+/// Clones of a handle share the same slot, so a `replace` through one clone
+/// is seen by all of them. Readers take a snapshot with `get`, which remains
+/// valid (and unchanged) even if the handle is swapped afterwards.
+///
+/// Independent handles can hold independent Pokedexes, e.g. one with the
+/// included data and one with house rules.
+#[derive(Clone)]
+pub struct PokedexHandle(Arc<RwLock<Arc<Pokedex>>>);
+
+impl PokedexHandle {
+    /// Create a new handle holding the Pokedex.
+    pub fn new(dex: Pokedex) -> Self {
+        Self::from_arc(Arc::new(dex))
+    }
+
+    /// Create a new handle holding an already-shared Pokedex.
+    pub fn from_arc(dex: Arc<Pokedex>) -> Self {
+        PokedexHandle(Arc::new(RwLock::new(dex)))
+    }
+
+    /// Get a snapshot of the current Pokedex.
+    pub fn get(&self) -> Arc<Pokedex> {
+        self.0.read().unwrap().clone()
+    }
+
+    /// Swap in a new Pokedex, returning the previous one.
+    pub fn replace(&self, dex: Pokedex) -> Arc<Pokedex> {
+        self.replace_arc(Arc::new(dex))
+    }
+
+    /// Swap in an already-shared Pokedex, returning the previous one.
+    pub fn replace_arc(&self, dex: Arc<Pokedex>) -> Arc<Pokedex> {
+        std::mem::replace(&mut *self.0.write().unwrap(), dex)
+    }
+
+    /// Load a new Pokedex from the source and swap it in, returning the
+    /// previous one.
+    ///
+    /// The handle is left unchanged if loading fails.
+    pub fn reload(
+        &self, source: &DataSource
+    ) -> Result<Arc<Pokedex>, LoadError> {
+        Ok(self.replace(Pokedex::from_source(source)?))
+    }
+}
+
+impl Default for PokedexHandle {
+    /// Create a new handle holding the global Pokedex instance.
+    fn default() -> Self {
+        Self::from_arc(crate::pokedex_arc())
+    }
+}
@@ -1,9 +1,10 @@
 use crate::enums::*;
 use crate::FromVeekun;
 use super::ItemId;
+use crate::DataSource;
+use crate::LoadError;
 use crate::Type;
 use crate::vcsv;
-use crate::vcsv::FromCsv;
 use crate::vdata;
 
 /// Aka condition, the "type" of moves in contests.
@@ -121,6 +122,7 @@ impl FromVeekun for BerryId {
 /// > where their various effects include HP and status condition restoration,
 /// > stat enhancement, and even damage negation.
 #[derive(Copy, Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Berry {
     pub item: ItemId,
     pub natural_gift_power: u8,
@@ -131,10 +133,10 @@ pub struct Berry {
 pub struct BerryTable(pub [Berry; BERRY_COUNT]);
 
 impl BerryTable {
-    pub fn new() -> Self {
-        let mut table = BerryTable::from_csv_data(vdata::BERRIES).unwrap();
-        table.set_flavors(&BerryFlavorTable::new());
-        table
+    pub fn from_source(source: &DataSource) -> Result<Self, LoadError> {
+        let mut table: Self = source.load(vdata::files::BERRIES)?;
+        table.set_flavors(&BerryFlavorTable::from_source(source)?);
+        Ok(table)
     }
 
     fn set_flavors(&mut self, flavors: &BerryFlavorTable) {
@@ -201,8 +203,8 @@ pub struct BerryFlavorTable {
 }
 
 impl BerryFlavorTable {
-    pub fn new() -> Self {
-        BerryFlavorTable::from_csv_data(vdata::BERRY_FLAVORS).unwrap()
+    pub fn from_source(source: &DataSource) -> Result<Self, LoadError> {
+        source.load(vdata::files::BERRY_FLAVORS)
     }
 }
 
@@ -1,8 +1,9 @@
 use crate::FromVeekun;
 use std::collections::HashMap;
 use super::ItemId;
+use crate::DataSource;
+use crate::LoadError;
 use crate::vcsv;
-use crate::vcsv::FromCsv;
 use crate::vdata;
 
 bitflags! {
@@ -46,8 +47,8 @@ impl FromVeekun for Flags {
 pub struct FlagTable(pub HashMap<ItemId, Flags>);
 
 impl FlagTable {
-    pub fn new() -> Self {
-        FlagTable::from_csv_data(vdata::ITEM_FLAGS).unwrap()
+    pub fn from_source(source: &DataSource) -> Result<Self, LoadError> {
+        source.load(vdata::files::ITEM_FLAGS)
     }
 }
 
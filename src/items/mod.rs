@@ -11,14 +11,16 @@ pub use self::berries::BERRY_COUNT;
 pub use self::berries::Flavor;
 pub use self::flags::Flags;
 
-use std::collections::HashMap;
+use std::collections::{hash_map, HashMap};
 use crate::enums::*;
 use crate::FromVeekun;
+use crate::DataSource;
+use crate::LoadError;
 use crate::to_pascal_case;
 use crate::vcsv;
-use crate::vcsv::FromCsv;
 use crate::vdata;
 use crate::VeekunOption;
+use crate::names::NameIndex;
 
 /// Extra effect when thrown using Fling.
 #[EnumRepr(type = "u8")]
@@ -72,6 +74,7 @@ impl FromVeekun for ItemId {
 /// > various uses, including healing, powering up, helping one to catch
 /// > Pokémon, or to access a new area.
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Item {
     /// The pbirch id for the item.
     pub id: ItemId,
@@ -95,17 +98,75 @@ pub struct Item {
 
 /// Wrapper of a `HashMap` mapping IDs to items.
 ///
-/// Use `table.0` to access `HashMap` members.
+/// Use `table.0` to access `HashMap` members. After renaming items through
+/// it, call `reindex`.
 #[derive(Default)]
-pub struct ItemTable(pub HashMap<ItemId, Item>);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ItemTable(
+    pub HashMap<ItemId, Item>,
+    #[cfg_attr(feature = "serde", serde(skip))] NameIndex<ItemId>,
+);
 
 impl ItemTable {
     /// Create an item table from the included CSV data.
     pub fn new() -> Self {
-        let mut items_table = ItemTable::from_csv_data(vdata::ITEMS).unwrap();
-        items_table.set_berries(&berries::BerryTable::new());
-        items_table.set_flags(&flags::FlagTable::new());
-        items_table
+        Self::try_new().unwrap()
+    }
+
+    /// Like `new`, but returns an error instead of panicking.
+    pub fn try_new() -> Result<Self, LoadError> {
+        Self::from_source(&DataSource::Embedded)
+    }
+
+    /// Create the table from the CSV files in the given source.
+    pub fn from_source(source: &DataSource) -> Result<Self, LoadError> {
+        let mut items_table: Self = source.load(vdata::files::ITEMS)?;
+        items_table.set_berries(&berries::BerryTable::from_source(source)?);
+        items_table.set_flags(&flags::FlagTable::from_source(source)?);
+        items_table.1.build(items_table.names());
+        Ok(items_table)
+    }
+
+    /// Iterate over all items, in no particular order.
+    pub fn iter(&self) -> hash_map::Values<'_, ItemId, Item> {
+        self.0.values()
+    }
+
+    /// The number of items.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the table has no items.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Get an item by ID, or `None` if no item has the ID.
+    pub fn get(&self, id: ItemId) -> Option<&Item> {
+        self.0.get(&id)
+    }
+
+    /// Find an item by name, ignoring case and punctuation (see
+    /// `veekun::names_match`).
+    pub fn by_name(&self, name: &str) -> Option<&Item> {
+        self.1.get(name, || self.names()).and_then(|id| self.get(id))
+    }
+
+    /// Rebuild the name index used by `by_name`, e.g. after renaming items.
+    pub fn reindex(&mut self) {
+        self.1.clear();
+        self.1.build(self.names());
+    }
+
+    /// The names and IDs of all items, in order of ID so that the index keeps
+    /// the same item when names collide.
+    fn names(&self) -> impl Iterator<Item = (&str, ItemId)> {
+        let mut names: Vec<_> = self.iter()
+            .map(|i| (i.name.as_str(), i.id))
+            .collect();
+        names.sort_by_key(|&(_, id)| id);
+        names.into_iter()
     }
 
     fn set_berries(&mut self, berry_table: &berries::BerryTable) {
@@ -154,3 +215,12 @@ impl std::ops::Index<ItemId> for ItemTable {
         self.0.index(&index)
     }
 }
+
+impl<'a> IntoIterator for &'a ItemTable {
+    type Item = &'a Item;
+    type IntoIter = hash_map::Values<'a, ItemId, Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
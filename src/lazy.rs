@@ -0,0 +1,130 @@
+use once_cell::sync::OnceCell;
+use crate::DataSource;
+use crate::EfficacyTable;
+use crate::items::ItemTable;
+use crate::LoadError;
+use crate::moves::MoveTable;
+use crate::PalaceTable;
+use crate::pokemon::{PokemonMoveTable, SpeciesTable};
+
+/// A Pokedex whose tables are each loaded on first access.
+///
+/// Initialization is thread-safe: if several threads access a table that has
+/// not been loaded, it is loaded once and the other threads wait for it.
+///
+/// The species are loaded without learnsets, so `Pokemon.moves` is always
+/// empty here; use `learnsets` instead. Call `preload_all` to pay the whole
+/// cost up front.
+pub struct LazyPokedex {
+    source: DataSource,
+    efficacy: OnceCell<EfficacyTable>,
+    items: OnceCell<ItemTable>,
+    moves: OnceCell<MoveTable>,
+    palace: OnceCell<PalaceTable>,
+    species: OnceCell<SpeciesTable>,
+    learnsets: OnceCell<PokemonMoveTable>,
+}
+
+impl LazyPokedex {
+    /// Create a lazy Pokedex for the included Veekun CSV data.
+    pub fn new() -> Self {
+        Self::from_source(DataSource::Embedded)
+    }
+
+    /// Create a lazy Pokedex for the CSV files in the given source.
+    pub fn from_source(source: DataSource) -> Self {
+        LazyPokedex {
+            source,
+            efficacy: OnceCell::new(),
+            items: OnceCell::new(),
+            moves: OnceCell::new(),
+            palace: OnceCell::new(),
+            species: OnceCell::new(),
+            learnsets: OnceCell::new(),
+        }
+    }
+
+    /// The source the tables are loaded from.
+    pub fn source(&self) -> &DataSource {
+        &self.source
+    }
+
+    /// Load every table that has not been loaded yet.
+    pub fn preload_all(&self) -> Result<(), LoadError> {
+        self.try_efficacy()?;
+        self.try_items()?;
+        self.try_moves()?;
+        self.try_palace()?;
+        self.try_species()?;
+        self.try_learnsets()?;
+        Ok(())
+    }
+
+    /// Get the efficacy table, loading it if needed. Panics on error.
+    pub fn efficacy(&self) -> &EfficacyTable {
+        self.try_efficacy().unwrap()
+    }
+
+    /// Get the efficacy table, loading it if needed.
+    pub fn try_efficacy(&self) -> Result<&EfficacyTable, LoadError> {
+        self.efficacy.get_or_try_init(
+            || EfficacyTable::from_source(&self.source))
+    }
+
+    /// Get the item table, loading it if needed. Panics on error.
+    pub fn items(&self) -> &ItemTable {
+        self.try_items().unwrap()
+    }
+
+    /// Get the item table, loading it if needed.
+    pub fn try_items(&self) -> Result<&ItemTable, LoadError> {
+        self.items.get_or_try_init(|| ItemTable::from_source(&self.source))
+    }
+
+    /// Get the move table, loading it if needed. Panics on error.
+    pub fn moves(&self) -> &MoveTable {
+        self.try_moves().unwrap()
+    }
+
+    /// Get the move table, loading it if needed.
+    pub fn try_moves(&self) -> Result<&MoveTable, LoadError> {
+        self.moves.get_or_try_init(|| MoveTable::from_source(&self.source))
+    }
+
+    /// Get the palace table, loading it if needed. Panics on error.
+    pub fn palace(&self) -> &PalaceTable {
+        self.try_palace().unwrap()
+    }
+
+    /// Get the palace table, loading it if needed.
+    pub fn try_palace(&self) -> Result<&PalaceTable, LoadError> {
+        self.palace.get_or_try_init(|| PalaceTable::from_source(&self.source))
+    }
+
+    /// Get the species table (without learnsets), loading it if needed.
+    /// Panics on error.
+    pub fn species(&self) -> &SpeciesTable {
+        self.try_species().unwrap()
+    }
+
+    /// Get the species table (without learnsets), loading it if needed.
+    pub fn try_species(&self) -> Result<&SpeciesTable, LoadError> {
+        self.species.get_or_try_init(
+            || SpeciesTable::from_source_without_moves(&self.source))
+    }
+
+    /// Get the learnset table, loading it if needed. Panics on error.
+    pub fn learnsets(&self) -> &PokemonMoveTable {
+        self.try_learnsets().unwrap()
+    }
+
+    /// Get the learnset table, loading it if needed.
+    pub fn try_learnsets(&self) -> Result<&PokemonMoveTable, LoadError> {
+        self.learnsets.get_or_try_init(
+            || PokemonMoveTable::from_source(&self.source))
+    }
+}
+
+impl Default for LazyPokedex {
+    fn default() -> Self { Self::new() }
+}
@@ -1,22 +1,46 @@
 #[macro_use]
 extern crate bitflags;
 extern crate enum_repr;
+extern crate once_cell;
 extern crate veekun;
 
 pub(self) mod abilities;
+pub mod capture;
+pub mod damage;
 pub(self) mod enums;
+pub mod evolution;
+pub mod experience;
+pub(self) mod handle;
 pub mod items;
+pub(self) mod lazy;
+pub(self) mod load;
 pub mod moves;
+pub(self) mod names;
 pub(self) mod natures;
+pub mod overlay;
 pub mod pokemon;
+pub mod search;
+pub mod stats;
+#[cfg(feature = "serde")]
+pub(self) mod serialize;
+pub mod team;
 pub(self) mod types;
 pub mod versions;
+pub mod weight;
 
 pub use self::abilities::Ability;
+pub use self::enums::ByName;
 pub use self::enums::Enum;
+pub use self::enums::ParseEnumError;
+pub use self::handle::PokedexHandle;
+pub use self::lazy::LazyPokedex;
+pub use self::load::DataSource;
+pub use self::load::LoadError;
 pub use self::natures::*;
 pub use self::types::*;
 
+use std::sync::Arc;
+use once_cell::sync::OnceCell;
 use veekun::csv as vcsv;
 use veekun::data as vdata;
 use veekun::repr::{FromVeekun, VeekunOption};
@@ -26,6 +50,7 @@ use veekun::to_pascal_case;
 mod tests;
 
 /// All the data in vdex.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Pokedex {
     pub efficacy: EfficacyTable,
     pub items: items::ItemTable,
@@ -35,26 +60,43 @@ pub struct Pokedex {
 }
 
 impl Pokedex {
+    /// Load all the tables from the included Veekun CSV data.
+    ///
+    /// Panics if any of the data fails to load. See `try_new`.
     pub fn new() -> Self {
-        Pokedex {
-            efficacy: EfficacyTable::new(),
-            items: items::ItemTable::new(),
-            moves: moves::MoveTable::new(),
-            palace: PalaceTable::new(),
-            species: pokemon::SpeciesTable::new(),
-        }
+        Self::try_new().unwrap()
+    }
+
+    /// Load all the tables from the included Veekun CSV data, returning the
+    /// first error encountered.
+    pub fn try_new() -> Result<Self, LoadError> {
+        Self::from_source(&DataSource::Embedded)
+    }
+
+    /// Load all the tables from the CSV files in the given source, returning
+    /// the first error encountered.
+    pub fn from_source(source: &DataSource) -> Result<Self, LoadError> {
+        Ok(Pokedex {
+            efficacy: EfficacyTable::from_source(source)?,
+            items: items::ItemTable::from_source(source)?,
+            moves: moves::MoveTable::from_source(source)?,
+            palace: PalaceTable::from_source(source)?,
+            species: pokemon::SpeciesTable::from_source(source)?,
+        })
     }
 }
 
-static mut POKEDEX: Option<Pokedex> = None;
-static POKEDEX_ONCE: std::sync::Once = std::sync::Once::new();
+static POKEDEX: OnceCell<Arc<Pokedex>> = OnceCell::new();
 
 /// START HERE: Load (if not loaded) and return the global Pokedex instance.
+///
+/// The global instance always holds the included Veekun data. To use other
+/// data, or to swap data at runtime, see `PokedexHandle`.
 pub fn pokedex() -> &'static Pokedex {
-    unsafe {
-        POKEDEX_ONCE.call_once(|| {
-            POKEDEX = Some(Pokedex::new());
-        });
-        POKEDEX.as_ref().unwrap()
-    }
+    POKEDEX.get_or_init(|| Arc::new(Pokedex::new()))
+}
+
+/// Like `pokedex`, but returns a reference-counted pointer.
+pub fn pokedex_arc() -> Arc<Pokedex> {
+    POKEDEX.get_or_init(|| Arc::new(Pokedex::new())).clone()
 }
@@ -0,0 +1,81 @@
+//! Errors and data sources for loading the Pokédex from Veekun CSV data.
+
+use std::collections::HashMap;
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::PathBuf;
+use crate::vcsv;
+use crate::vcsv::FromCsv;
+use crate::vdata;
+
+/// Error loading a Pokédex table, tagged with the CSV file it came from.
+#[derive(Debug)]
+pub struct LoadError {
+    /// The name of the CSV file (e.g. `"moves.csv"`).
+    pub file: &'static str,
+    /// The underlying CSV error.
+    pub error: vcsv::Error,
+}
+
+impl LoadError {
+    pub fn new(file: &'static str, error: vcsv::Error) -> Self {
+        LoadError { file, error }
+    }
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}: {}", self.file, self.error)
+    }
+}
+
+impl StdError for LoadError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Where the Veekun CSV files are loaded from.
+///
+/// Files are named as in `veekun::data::files` (e.g. `"moves.csv"`).
+#[derive(Clone, Debug)]
+pub enum DataSource {
+    /// The CSV data included in the binary.
+    Embedded,
+    /// A directory on disk containing all of the CSV files.
+    Directory(PathBuf),
+    /// A map of file names to CSV file contents.
+    Memory(HashMap<String, Vec<u8>>),
+}
+
+impl DataSource {
+    /// Load a table from the named CSV file, tagging any error with the file
+    /// name.
+    ///
+    /// A file missing from a `Memory` source is reported the same way as one
+    /// missing from a `Directory`, as an I/O error of kind `NotFound`.
+    pub fn load<T: FromCsv>(&self, file: &'static str) -> Result<T, LoadError> {
+        let result = match self {
+            DataSource::Embedded => match vdata::get(file) {
+                Some(data) => T::from_csv_data(data),
+                None => Err(not_found(file)),
+            },
+            DataSource::Directory(dir) => T::from_csv_file(&dir.join(file)),
+            DataSource::Memory(files) => match files.get(file) {
+                Some(data) => T::from_csv_data(data),
+                None => Err(not_found(file)),
+            },
+        };
+        result.map_err(|e| LoadError::new(file, e))
+    }
+}
+
+impl Default for DataSource {
+    fn default() -> Self { DataSource::Embedded }
+}
+
+fn not_found(file: &str) -> vcsv::Error {
+    let message = format!("No data for {}.", file);
+    csv::Error::from(io::Error::new(io::ErrorKind::NotFound, message)).into()
+}
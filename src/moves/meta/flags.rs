@@ -1,7 +1,8 @@
 use std::collections::HashMap;
 use crate::FromVeekun;
+use crate::DataSource;
+use crate::LoadError;
 use crate::vcsv;
-use crate::vcsv::FromCsv;
 use crate::vdata;
 use super::MoveId;
 
@@ -58,8 +59,8 @@ impl FromVeekun for Flags {
 pub struct FlagTable(pub HashMap<MoveId, Flags>);
 
 impl FlagTable {
-    pub fn new() -> Self {
-        FlagTable::from_csv_data(vdata::MOVE_FLAGS).unwrap()
+    pub fn from_source(source: &DataSource) -> Result<Self, LoadError> {
+        source.load(vdata::files::MOVE_FLAGS)
     }
 }
 
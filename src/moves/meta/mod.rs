@@ -8,10 +8,11 @@ pub use self::flags::FlagTable;
 use std::collections::HashMap;
 use crate::enums::*;
 use crate::FromVeekun;
+use crate::DataSource;
+use crate::LoadError;
 use crate::moves::MOVE_COUNT;
 use crate::Stat;
 use crate::vcsv;
-use crate::vcsv::FromCsv;
 use crate::vdata;
 use crate::VeekunOption;
 use super::MoveId;
@@ -69,8 +70,8 @@ pub const CHANGEABLE_STATS: usize = 7;
 pub struct StatChangeTable(pub HashMap<MoveId, [i8; CHANGEABLE_STATS]>);
 
 impl StatChangeTable {
-    pub fn new() -> Self {
-        StatChangeTable::from_csv_data(vdata::MOVE_STAT_CHANGES).unwrap()
+    pub fn from_source(source: &DataSource) -> Result<Self, LoadError> {
+        source.load(vdata::files::MOVE_STAT_CHANGES)
     }
 }
 
@@ -96,6 +97,7 @@ impl vcsv::FromCsvIncremental for StatChangeTable {
 
 /// Namespace for move data deemed "meta."
 #[derive(Copy, Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Meta {
     /// The move category.
     pub category: Category,
@@ -128,11 +130,11 @@ pub struct Meta {
 pub struct MetaTable(pub [Meta; MOVE_COUNT]);
 
 impl MetaTable {
-    pub fn new() -> Self {
-        let mut table = MetaTable::from_csv_data(vdata::MOVE_META).unwrap();
-        table.set_flags(&FlagTable::new());
-        table.set_stat_changes(&StatChangeTable::new());
-        table
+    pub fn from_source(source: &DataSource) -> Result<Self, LoadError> {
+        let mut table: Self = source.load(vdata::files::MOVE_META)?;
+        table.set_flags(&FlagTable::from_source(source)?);
+        table.set_stat_changes(&StatChangeTable::from_source(source)?);
+        Ok(table)
     }
 
     fn set_flags(&mut self, flags_table: &FlagTable) {
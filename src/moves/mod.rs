@@ -13,13 +13,15 @@ pub use self::meta::CHANGEABLE_STATS;
 use std::iter::repeat;
 use crate::enums::*;
 use crate::FromVeekun;
+use crate::DataSource;
+use crate::LoadError;
 use crate::to_pascal_case;
 use crate::Type;
 use crate::vcsv;
-use crate::vcsv::FromCsv;
 use crate::vdata;
 use crate::VeekunOption;
 use crate::versions::Generation;
+use crate::names::NameIndex;
 
 /// The Battle Palace style of a move.
 #[EnumRepr(type = "u8")]
@@ -171,6 +173,7 @@ impl FromVeekun for MoveId {
 /// > special technique), is the skill Pokémon primarily use in battle. In
 /// > battle, a Pokémon uses one move each turn.
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Move {
     /// The pbirch id for the move.
     pub id: MoveId,
@@ -200,19 +203,85 @@ pub struct Move {
     pub meta: meta::Meta,
 }
 
+impl Move {
+    /// The move's type in the given generation.
+    ///
+    /// Karate Chop, Gust, Sand Attack, and Bite were Normal-type moves in
+    /// Generation I.
+    pub fn type_in(&self, generation: Generation) -> Type {
+        match self.id {
+            MoveId(1) | MoveId(15) | MoveId(27) | MoveId(43)
+                if generation == Generation::I => Type::Normal,
+            _ => self.typ,
+        }
+    }
+}
+
 /// Wrapper of a `Vec` for all moves.
 ///
 /// A move's index is its Veekun ID minus 1.
 ///
-/// Use `table.0` to access `Vec` members.
-pub struct MoveTable(pub Vec<Move>);
+/// Use `table.0` to access `Vec` members. After renaming moves through it,
+/// call `reindex`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MoveTable(
+    pub Vec<Move>,
+    #[cfg_attr(feature = "serde", serde(skip))] NameIndex<MoveId>,
+);
 
 impl MoveTable {
     /// Create a move table from the included Veekun CSV data.
     pub fn new() -> Self {
-        let mut table = MoveTable::from_csv_data(vdata::MOVES).unwrap();
-        table.set_meta(&meta::MetaTable::new());
-        table
+        Self::try_new().unwrap()
+    }
+
+    /// Like `new`, but returns an error instead of panicking.
+    pub fn try_new() -> Result<Self, LoadError> {
+        Self::from_source(&DataSource::Embedded)
+    }
+
+    /// Create the table from the CSV files in the given source.
+    pub fn from_source(source: &DataSource) -> Result<Self, LoadError> {
+        let mut table: Self = source.load(vdata::files::MOVES)?;
+        table.set_meta(&meta::MetaTable::from_source(source)?);
+        table.1.build(table.names());
+        Ok(table)
+    }
+
+    /// Iterate over all moves in ID order.
+    pub fn iter(&self) -> std::slice::Iter<'_, Move> {
+        self.0.iter()
+    }
+
+    /// The number of moves.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the table has no moves.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Get a move by ID, or `None` if the ID is out of range.
+    pub fn get(&self, id: MoveId) -> Option<&Move> {
+        self.0.get(id.0 as usize)
+    }
+
+    /// Find a move by name, ignoring case and punctuation (see
+    /// `veekun::names_match`).
+    pub fn by_name(&self, name: &str) -> Option<&Move> {
+        self.1.get(name, || self.names()).map(|id| &self[id])
+    }
+
+    /// Rebuild the name index used by `by_name`, e.g. after renaming moves.
+    pub fn reindex(&mut self) {
+        self.1.clear();
+        self.1.build(self.names());
+    }
+
+    fn names(&self) -> impl Iterator<Item = (&str, MoveId)> {
+        self.iter().map(|m| (m.name.as_str(), m.id))
     }
 
     fn set_meta(&mut self, meta_table: &meta::MetaTable) {
@@ -224,7 +293,10 @@ impl MoveTable {
 
 impl Default for MoveTable {
     fn default() -> Self {
-        MoveTable(repeat(Default::default()).take(MOVE_COUNT).collect::<Vec<_>>())
+        MoveTable(
+            repeat(Default::default()).take(MOVE_COUNT).collect::<Vec<_>>(),
+            Default::default(),
+        )
     }
 }
 
@@ -272,3 +344,12 @@ impl std::ops::IndexMut<MoveId> for MoveTable {
         self.0.index_mut(index.0 as usize)
     }
 }
+
+impl<'a> IntoIterator for &'a MoveTable {
+    type Item = &'a Move;
+    type IntoIter = std::slice::Iter<'a, Move>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+use once_cell::sync::OnceCell;
+use veekun::name_key;
+
+/// Map from names, ignoring case and punctuation (see `veekun::names_match`),
+/// to the IDs of a table's entries.
+///
+/// Tables build the index when they are loaded, and otherwise (e.g. after
+/// deserialization) on the first lookup.
+#[derive(Clone, Debug, Default)]
+pub struct NameIndex<K>(OnceCell<HashMap<String, K>>);
+
+impl<K: Copy> NameIndex<K> {
+    /// Build the index from the names and IDs of the entries, unless it is
+    /// already built. The first entry with a given name is kept.
+    pub fn build<'a, I>(&self, entries: I)
+        where I: IntoIterator<Item = (&'a str, K)>
+    {
+        self.0.get_or_init(|| Self::map(entries));
+    }
+
+    /// Look up the ID for a name, first building the index from the entries
+    /// if it has not been built.
+    pub fn get<'a, I, F>(&self, name: &str, entries: F) -> Option<K>
+        where I: IntoIterator<Item = (&'a str, K)>, F: FnOnce() -> I
+    {
+        let key: String = name_key(name).collect();
+        self.0.get_or_init(|| Self::map(entries())).get(&key).cloned()
+    }
+
+    /// Discard the index, so that it is rebuilt on the next lookup.
+    pub fn clear(&mut self) {
+        self.0 = OnceCell::new();
+    }
+
+    fn map<'a, I>(entries: I) -> HashMap<String, K>
+        where I: IntoIterator<Item = (&'a str, K)>
+    {
+        let mut map = HashMap::new();
+        for (name, id) in entries {
+            map.entry(name_key(name).collect()).or_insert(id);
+        }
+        map
+    }
+}
@@ -1,8 +1,9 @@
 use crate::enums::*;
 use crate::moves::BattleStyle;
 use crate::items::Flavor;
+use crate::DataSource;
+use crate::LoadError;
 use crate::vcsv;
-use crate::vcsv::FromCsv;
 use crate::vdata;
 use crate::FromVeekun;
 
@@ -168,6 +169,7 @@ impl FromVeekun for Stat {
 
 /// Half of the table determining Battle Palace behavior. See `PalaceTable`.
 #[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HalfPalaceTable {
     pub attack: [u8; Nature::COUNT],
     pub defense: [u8; Nature::COUNT],
@@ -186,12 +188,24 @@ impl HalfPalaceTable {
             _ => BattleStyle::Support,
         }
     }
+
+    /// The percentage chance of picking a battle style.
+    pub fn weight(&self, nature: Nature, style: BattleStyle) -> u8 {
+        let i = nature.repr() as usize;
+        match style {
+            BattleStyle::Attack => self.attack[i],
+            BattleStyle::Defense => self.defense[i],
+            BattleStyle::Support
+                => 100u8.saturating_sub(self.attack[i] + self.defense[i]),
+        }
+    }
 }
 
 /// Table of probabilities determining Battle Palace behavior.
 ///
 /// There are two half tables, one for when HP is below half, one for otherwise.
 #[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PalaceTable {
     pub low: HalfPalaceTable,
     pub high: HalfPalaceTable,
@@ -200,10 +214,84 @@ pub struct PalaceTable {
 impl PalaceTable {
     /// Create a palace table from the included Veekun CSV data.
     pub fn new() -> Self {
-        Self::from_csv_data(vdata::PALACE).unwrap()
+        Self::try_new().unwrap()
+    }
+
+    /// Like `new`, but returns an error instead of panicking.
+    pub fn try_new() -> Result<Self, LoadError> {
+        Self::from_source(&DataSource::Embedded)
+    }
+
+    /// Create the table from the CSV files in the given source.
+    pub fn from_source(source: &DataSource) -> Result<Self, LoadError> {
+        source.load(vdata::files::PALACE)
+    }
+
+    /// Iterate over all (nature, battle style) combinations and their
+    /// percentage chances, below and above half HP.
+    pub fn iter(&self) -> PalaceIter<'_> {
+        PalaceIter { table: self, next: 0 }
+    }
+
+    /// The number of (nature, battle style) combinations.
+    pub fn len(&self) -> usize {
+        Nature::COUNT * BattleStyle::COUNT
+    }
+
+    /// Whether the table has no combinations, which is never the case.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Get the percentage chances of a (nature, battle style) combination,
+    /// below and above half HP.
+    ///
+    /// The table covers every combination, so this is always `Some`; it is
+    /// provided for symmetry with the other tables.
+    pub fn get(&self, index: (Nature, BattleStyle)) -> Option<(u8, u8)> {
+        let (nature, style) = index;
+        Some((self.low.weight(nature, style), self.high.weight(nature, style)))
+    }
+}
+
+impl<'a> IntoIterator for &'a PalaceTable {
+    type Item = ((Nature, BattleStyle), (u8, u8));
+    type IntoIter = PalaceIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the combinations of a `PalaceTable`.
+#[derive(Clone)]
+pub struct PalaceIter<'a> {
+    table: &'a PalaceTable,
+    next: usize,
+}
+
+impl<'a> Iterator for PalaceIter<'a> {
+    type Item = ((Nature, BattleStyle), (u8, u8));
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= Nature::COUNT * BattleStyle::COUNT {
+            return None;
+        }
+        let nature = Nature::VALUES[self.next / BattleStyle::COUNT];
+        let style = BattleStyle::VALUES[self.next % BattleStyle::COUNT];
+        self.next += 1;
+        let weights = self.table.get((nature, style))?;
+        Some(((nature, style), weights))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = Nature::COUNT * BattleStyle::COUNT - self.next;
+        (left, Some(left))
     }
 }
 
+impl<'a> ExactSizeIterator for PalaceIter<'a> {}
+
 impl vcsv::FromCsvIncremental for PalaceTable {
     fn from_empty_csv() -> Self { Default::default() }
 
@@ -0,0 +1,270 @@
+//! Patches applied on top of a loaded Pokédex, e.g. for house rules.
+//!
+//! An overlay can be built from `Patch` values directly, or loaded from a CSV
+//! file with the columns `table,id,field,value`. IDs and values use the same
+//! Veekun representations as the included data. The supported records are:
+//!
+//! - `move,<move_id>,<field>,<value>`, where `field` is one of `power`, `pp`,
+//!   `accuracy`, `priority`, `type_id`, `damage_class_id`, or
+//!   `effect_chance` (as in `moves.csv`).
+//! - `efficacy,<damage_type_id>,<target_type_id>,<damage_factor>` (as in
+//!   `type_efficacy.csv`).
+//! - `stat,<pokemon_id>,<stat_id>,<base_stat>` (as in `pokemon_stats.csv`).
+//! - `item,<item_id>,<field>,<value>`, where `field` is one of `cost` or
+//!   `fling_power` (as in `items.csv`).
+//!
+//! For example, the following overlay sets the power of Tackle to 50 and makes
+//! Fire attacks regularly effective against Steel:
+//!
+//! ```text
+//! table,id,field,value
+//! move,33,power,50
+//! efficacy,10,9,100
+//! ```
+
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter};
+use crate::Efficacy;
+use crate::items::ItemId;
+use crate::moves::{DamageClass, MoveId};
+use crate::pokemon::PokemonId;
+use crate::Pokedex;
+use crate::Stat;
+use crate::Type;
+use crate::vcsv;
+use crate::VeekunOption;
+
+/// A single change to the Pokédex data.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Patch {
+    /// Set a move's power.
+    MovePower(MoveId, u8),
+    /// Set a move's power points.
+    MovePP(MoveId, u8),
+    /// Set a move's accuracy (`None` if it cannot miss).
+    MoveAccuracy(MoveId, Option<u8>),
+    /// Set a move's priority.
+    MovePriority(MoveId, i8),
+    /// Set a move's type.
+    MoveType(MoveId, Type),
+    /// Set a move's damage class.
+    MoveDamageClass(MoveId, DamageClass),
+    /// Set a move's effect chance.
+    MoveEffectChance(MoveId, Option<u8>),
+    /// Set the efficacy of a (damage, target) type combination.
+    Efficacy(Type, Type, Efficacy),
+    /// Set one of a Pokémon's base permanent stats.
+    BaseStat(PokemonId, Stat, u8),
+    /// Set the cost of an item.
+    ItemCost(ItemId, u16),
+    /// Set the power of Fling with an item (`None` if it cannot be flung).
+    ItemFlingPower(ItemId, Option<u8>),
+}
+
+impl Patch {
+    /// True if everything the patch refers to exists in the Pokédex.
+    pub fn is_valid(&self, dex: &Pokedex) -> bool {
+        match *self {
+            Patch::MovePower(id, _) | Patch::MovePP(id, _)
+                | Patch::MoveAccuracy(id, _) | Patch::MovePriority(id, _)
+                | Patch::MoveType(id, _) | Patch::MoveDamageClass(id, _)
+                | Patch::MoveEffectChance(id, _)
+                => (id.0 as usize) < dex.moves.0.len(),
+            Patch::Efficacy(_, _, _) => true,
+            Patch::BaseStat(id, stat, _) => match stat {
+                Stat::Accuracy | Stat::Evasion => false,
+                _ => dex.species.find_pokemon(id).is_some(),
+            },
+            Patch::ItemCost(id, _) | Patch::ItemFlingPower(id, _)
+                => dex.items.0.contains_key(&id),
+        }
+    }
+
+    /// Apply the patch, returning a patch that would restore the previous
+    /// value.
+    ///
+    /// Panics if the patch is not valid. See `is_valid`.
+    pub fn apply(&self, dex: &mut Pokedex) -> Patch {
+        match *self {
+            Patch::MovePower(id, power) => {
+                let old = std::mem::replace(&mut dex.moves[id].power, power);
+                Patch::MovePower(id, old)
+            },
+            Patch::MovePP(id, pp) => {
+                let old = std::mem::replace(&mut dex.moves[id].pp, pp);
+                Patch::MovePP(id, old)
+            },
+            Patch::MoveAccuracy(id, accuracy) => {
+                let old = std::mem::replace(
+                    &mut dex.moves[id].accuracy, accuracy);
+                Patch::MoveAccuracy(id, old)
+            },
+            Patch::MovePriority(id, priority) => {
+                let old = std::mem::replace(
+                    &mut dex.moves[id].priority, priority);
+                Patch::MovePriority(id, old)
+            },
+            Patch::MoveType(id, typ) => {
+                let old = std::mem::replace(&mut dex.moves[id].typ, typ);
+                Patch::MoveType(id, old)
+            },
+            Patch::MoveDamageClass(id, class) => {
+                let old = std::mem::replace(
+                    &mut dex.moves[id].damage_class, class);
+                Patch::MoveDamageClass(id, old)
+            },
+            Patch::MoveEffectChance(id, chance) => {
+                let old = std::mem::replace(
+                    &mut dex.moves[id].effect_chance, chance);
+                Patch::MoveEffectChance(id, old)
+            },
+            Patch::Efficacy(damage, target, efficacy) => {
+                let old = std::mem::replace(
+                    &mut dex.efficacy[(damage, target)], efficacy);
+                Patch::Efficacy(damage, target, old)
+            },
+            Patch::BaseStat(id, stat, base) => {
+                let pokemon = dex.species.find_pokemon_mut(id).unwrap();
+                let old = std::mem::replace(&mut pokemon.stats[stat], base);
+                Patch::BaseStat(id, stat, old)
+            },
+            Patch::ItemCost(id, cost) => {
+                let item = dex.items.0.get_mut(&id).unwrap();
+                let old = std::mem::replace(&mut item.cost, cost);
+                Patch::ItemCost(id, old)
+            },
+            Patch::ItemFlingPower(id, power) => {
+                let item = dex.items.0.get_mut(&id).unwrap();
+                let old = std::mem::replace(&mut item.fling_power, power);
+                Patch::ItemFlingPower(id, old)
+            },
+        }
+    }
+}
+
+/// A record of a patch that was applied by an overlay.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Override {
+    /// The patch that was applied.
+    pub patch: Patch,
+    /// A patch that would restore the value from before `patch` was applied.
+    pub previous: Patch,
+}
+
+/// Error applying an overlay: a patch refers to something not in the Pokédex.
+#[derive(Copy, Clone, Debug)]
+pub struct OverlayError {
+    /// The index of the invalid patch in the overlay.
+    pub index: usize,
+    /// The invalid patch.
+    pub patch: Patch,
+}
+
+impl Display for OverlayError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "Patch {} refers to data not in the Pokédex: {:?}",
+               self.index, self.patch)
+    }
+}
+
+impl StdError for OverlayError { }
+
+/// An ordered list of patches.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Overlay(pub Vec<Patch>);
+
+impl Overlay {
+    /// Apply all the patches in order, returning an audit of what was
+    /// overridden.
+    ///
+    /// Every patch is validated before any are applied, so on error the
+    /// Pokédex is left unchanged.
+    pub fn apply(
+        &self, dex: &mut Pokedex
+    ) -> Result<Vec<Override>, OverlayError> {
+        for (index, patch) in self.0.iter().enumerate() {
+            if !patch.is_valid(dex) {
+                return Err(OverlayError { index, patch: *patch });
+            }
+        }
+        Ok(self.0.iter().map(|patch| Override {
+            patch: *patch,
+            previous: patch.apply(dex),
+        }).collect())
+    }
+}
+
+fn unknown(
+    record: &csv::StringRecord, field: usize, message: &'static str
+) -> vcsv::Error {
+    vcsv::Error::Veekun {
+        line: vcsv::get_line(record),
+        field,
+        error: Box::new(vcsv::MiscError(message)),
+    }
+}
+
+impl vcsv::FromCsvIncremental for Overlay {
+    fn from_empty_csv() -> Self { Default::default() }
+
+    fn load_csv_record(
+        &mut self, record: csv::StringRecord
+    ) -> vcsv::Result<()> {
+        let patch = match vcsv::get_field(&record, 0)? {
+            "move" => {
+                let id = vcsv::from_field(&record, 1)?;
+                match vcsv::get_field(&record, 2)? {
+                    "power" => Patch::MovePower(
+                        id, vcsv::from_field(&record, 3)?),
+                    "pp" => Patch::MovePP(id, vcsv::from_field(&record, 3)?),
+                    "accuracy" => {
+                        let accuracy: VeekunOption<_>
+                            = vcsv::from_field(&record, 3)?;
+                        Patch::MoveAccuracy(id, accuracy.into())
+                    },
+                    "priority" => Patch::MovePriority(
+                        id, vcsv::from_field(&record, 3)?),
+                    "type_id" => Patch::MoveType(
+                        id, vcsv::from_field(&record, 3)?),
+                    "damage_class_id" => Patch::MoveDamageClass(
+                        id, vcsv::from_field(&record, 3)?),
+                    "effect_chance" => {
+                        let chance: VeekunOption<_>
+                            = vcsv::from_field(&record, 3)?;
+                        Patch::MoveEffectChance(id, chance.into())
+                    },
+                    _ => return Err(unknown(&record, 2, "Unknown move field.")),
+                }
+            },
+            "efficacy" => Patch::Efficacy(
+                vcsv::from_field(&record, 1)?,
+                vcsv::from_field(&record, 2)?,
+                vcsv::from_field(&record, 3)?,
+            ),
+            "stat" => Patch::BaseStat(
+                vcsv::from_field(&record, 1)?,
+                vcsv::from_field(&record, 2)?,
+                vcsv::from_field(&record, 3)?,
+            ),
+            "item" => {
+                let id = vcsv::from_field(&record, 1)?;
+                match vcsv::get_field(&record, 2)? {
+                    "cost" => Patch::ItemCost(
+                        id, vcsv::from_field(&record, 3)?),
+                    "fling_power" => {
+                        let power: VeekunOption<_>
+                            = vcsv::from_field(&record, 3)?;
+                        Patch::ItemFlingPower(id, power.into())
+                    },
+                    _ => return Err(unknown(&record, 2, "Unknown item field.")),
+                }
+            },
+            _ => return Err(unknown(&record, 0, "Unknown table.")),
+        };
+        self.0.push(patch);
+        Ok(())
+    }
+}
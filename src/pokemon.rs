@@ -5,14 +5,19 @@ use std::iter::repeat;
 use crate::Ability;
 use crate::enums::*;
 use crate::FromVeekun;
+use crate::DataSource;
+use crate::{DefensiveProfile, EfficacyTable};
+use crate::LoadError;
+use crate::items::ItemId;
 use crate::moves::{LearnMethod, MoveId};
 use crate::Stat;
+use crate::stats::Spread;
 use crate::to_pascal_case;
 use crate::Type;
 use crate::vcsv;
-use crate::vcsv::FromCsv;
 use crate::vdata;
 use crate::VeekunOption;
+use crate::names::NameIndex;
 use veekun::repr::VeekunString;
 use crate::versions::{Generation, VersionGroup};
 
@@ -75,6 +80,17 @@ impl FromVeekun for EvolutionTrigger {
     }
 }
 
+/// The time of day required for an evolution.
+#[EnumRepr(type = "u8")]
+pub enum TimeOfDay {
+    Day = 0,
+    Night,
+}
+
+impl Default for TimeOfDay {
+    fn default() -> Self { TimeOfDay::Day }
+}
+
 /// Gender of a Pokémon.
 ///
 /// > [*[From Bulbapedia:]*](https://bulbapedia.bulbagarden.net/wiki/Gender) The
@@ -106,6 +122,159 @@ impl FromVeekun for Gender {
     }
 }
 
+/// The experience curve of a species.
+///
+/// > [*[From Bulbapedia:]*](https://bulbapedia.bulbagarden.net/wiki/Experience)
+/// > Each Pokémon species has a certain experience group which determines the
+/// > amount of experience it needs to reach each level.
+#[EnumRepr(type = "u8")]
+pub enum GrowthRate {
+    Slow = 1,
+    Medium,
+    Fast,
+    MediumSlow,
+    Erratic,
+    Fluctuating,
+}
+
+impl GrowthRate {
+    /// The total experience needed to reach a level from 1 to 100.
+    ///
+    /// Panics if the level is out of range.
+    pub fn experience(self, level: u8) -> u32 {
+        assert!((1..=100).contains(&level), "Invalid level {}", level);
+        if level == 1 {
+            return 0;
+        }
+        let n = level as i64;
+        let cube = n * n * n;
+        let experience = match self {
+            GrowthRate::Slow => 5 * cube / 4,
+            GrowthRate::Medium => cube,
+            GrowthRate::Fast => 4 * cube / 5,
+            GrowthRate::MediumSlow
+                => 6 * cube / 5 - 15 * n * n + 100 * n - 140,
+            GrowthRate::Erratic => match n {
+                _ if n < 50 => cube * (100 - n) / 50,
+                _ if n < 68 => cube * (150 - n) / 100,
+                _ if n < 98 => cube * ((1911 - 10 * n) / 3) / 500,
+                _ => cube * (160 - n) / 100,
+            },
+            GrowthRate::Fluctuating => match n {
+                _ if n < 15 => cube * ((n + 1) / 3 + 24) / 50,
+                _ if n < 36 => cube * (n + 14) / 50,
+                _ => cube * (n / 2 + 32) / 50,
+            },
+        };
+        experience as u32
+    }
+
+    /// The level of a Pokémon with the given total experience.
+    pub fn level(self, experience: u32) -> u8 {
+        (2..=100).take_while(|&l| self.experience(l) <= experience).last()
+            .unwrap_or(1)
+    }
+}
+
+impl Default for GrowthRate {
+    fn default() -> Self { GrowthRate::Medium }
+}
+
+impl FromVeekun for GrowthRate {
+    type Intermediate = u8;
+
+    fn from_veekun(value: u8) -> Option<Self> {
+        Self::from_repr(value)
+    }
+}
+
+/// The main color of a species, used to search the Pokédex.
+#[EnumRepr(type = "u8")]
+pub enum Color {
+    Black = 1,
+    Blue,
+    Brown,
+    Gray,
+    Green,
+    Pink,
+    Purple,
+    Red,
+    White,
+    Yellow,
+}
+
+impl Default for Color {
+    fn default() -> Self { Color::Black }
+}
+
+impl FromVeekun for Color {
+    type Intermediate = u8;
+
+    fn from_veekun(value: u8) -> Option<Self> {
+        Self::from_repr(value)
+    }
+}
+
+/// The body shape of a species, used to search the Pokédex from Generation
+/// IV onward.
+#[EnumRepr(type = "u8")]
+pub enum Shape {
+    Ball = 1,
+    Squiggle,
+    Fish,
+    Arms,
+    Blob,
+    Upright,
+    Legs,
+    Quadruped,
+    Wings,
+    Tentacles,
+    Heads,
+    Humanoid,
+    BugWings,
+    Armor,
+}
+
+impl Default for Shape {
+    fn default() -> Self { Shape::Ball }
+}
+
+impl FromVeekun for Shape {
+    type Intermediate = u8;
+
+    fn from_veekun(value: u8) -> Option<Self> {
+        Self::from_repr(value)
+    }
+}
+
+/// The habitat of a species, used to sort the FireRed and LeafGreen Pokédex.
+///
+/// Only species up to Generation III have a habitat.
+#[EnumRepr(type = "u8")]
+pub enum Habitat {
+    Cave = 1,
+    Forest,
+    Grassland,
+    Mountain,
+    Rare,
+    RoughTerrain,
+    Sea,
+    Urban,
+    WatersEdge,
+}
+
+impl Default for Habitat {
+    fn default() -> Self { Habitat::Rare }
+}
+
+impl FromVeekun for Habitat {
+    type Intermediate = u8;
+
+    fn from_veekun(value: u8) -> Option<Self> {
+        Self::from_repr(value)
+    }
+}
+
 /// Either one or two elements.
 #[derive(Copy, Clone, Debug)]
 pub enum OneOrTwo<T: Copy> {
@@ -170,8 +339,8 @@ impl FromVeekun for PokemonId {
 struct AbilityTable([[Option<Ability>; 3]; POKEMON_COUNT]);
 
 impl AbilityTable {
-    fn new() -> Self {
-        AbilityTable::from_csv_data(vdata::ABILITIES).unwrap()
+    fn from_source(source: &DataSource) -> Result<Self, LoadError> {
+        source.load(vdata::files::ABILITIES)
     }
 }
 
@@ -190,7 +359,7 @@ impl vcsv::FromCsvIncremental for AbilityTable {
         let id: PokemonId = vcsv::from_field(&record, 0)?;
         let ability = vcsv::from_field(&record, 1)?;
         let slot: usize = vcsv::from_field(&record, 3)?;
-        if slot < 1 && slot > 3 {
+        if !(1..=3).contains(&slot) {
             return Err(vcsv::Error::Veekun {
                 line: vcsv::get_line(&record),
                 field: 3,
@@ -217,6 +386,7 @@ impl std::ops::IndexMut<PokemonId> for AbilityTable {
 }
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Form {
     pub id: u16,
     pub name: Option<String>,
@@ -226,8 +396,8 @@ pub struct Form {
 struct FormTable(Vec<Vec<Form>>);
 
 impl FormTable {
-    fn new() -> Self {
-        FormTable::from_csv_data(vdata::FORMS).unwrap()
+    fn from_source(source: &DataSource) -> Result<Self, LoadError> {
+        source.load(vdata::files::FORMS)
     }
 }
 
@@ -270,18 +440,104 @@ impl std::ops::IndexMut<PokemonId> for FormTable {
     }
 }
 
-#[derive(Copy, Clone, Debug, Default)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PokemonMove {
     pub move_id: MoveId,
     pub learn_method: LearnMethod,
     pub level: u8,
 }
 
-struct PokemonMoveTable(Vec<HashMap<VersionGroup, Vec<PokemonMove>>>);
+/// The moves each Pokémon can learn, by version group.
+///
+/// This is the same data as `Pokemon.moves`, for when the learnsets are
+/// loaded separately from the rest of the species data (see
+/// `SpeciesTable::from_source_without_moves`).
+pub struct PokemonMoveTable(Vec<HashMap<VersionGroup, Vec<PokemonMove>>>);
 
 impl PokemonMoveTable {
-    fn new() -> Self {
-        PokemonMoveTable::from_csv_data(vdata::POKEMON_MOVES).unwrap()
+    /// Create a learnset table from the included Veekun CSV data.
+    pub fn new() -> Self {
+        Self::try_new().unwrap()
+    }
+
+    /// Like `new`, but returns an error instead of panicking.
+    pub fn try_new() -> Result<Self, LoadError> {
+        Self::from_source(&DataSource::Embedded)
+    }
+
+    /// Create the table from the CSV files in the given source.
+    ///
+    /// The included data is loaded from a precompiled snapshot rather than
+    /// parsed.
+    pub fn from_source(source: &DataSource) -> Result<Self, LoadError> {
+        let file = vdata::files::POKEMON_MOVES;
+        match source {
+            DataSource::Embedded
+                => Self::from_snapshot(vdata::POKEMON_MOVES_SNAPSHOT)
+                    .map_err(|e| LoadError::new(file, e)),
+            _ => source.load(file),
+        }
+    }
+
+    /// Iterate over the learnsets of all Pokémon, with their IDs.
+    pub fn iter(&self) -> LearnsetIter<'_> {
+        LearnsetIter(self.0.iter().enumerate())
+    }
+
+    /// The number of Pokémon in the table.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the table has no Pokémon.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Get the learnset of a Pokémon, or `None` if the ID is out of range.
+    pub fn get(
+        &self, id: PokemonId
+    ) -> Option<&HashMap<VersionGroup, Vec<PokemonMove>>> {
+        self.0.get(id.0 as usize)
+    }
+
+    /// Load the table from the binary form of `pokemon_moves.csv` produced by
+    /// the veekun build script.
+    ///
+    /// Errors are reported with the corresponding line in the CSV file.
+    fn from_snapshot(snapshot: &[u8]) -> vcsv::Result<Self> {
+        let mut table = Self::default();
+        let size = vdata::POKEMON_MOVES_RECORD_SIZE;
+        for (i, record) in snapshot.chunks(size).enumerate() {
+            let line = Some(i as u64 + 2);
+            if record.len() < size {
+                return Err(vcsv::Error::RecordLength {
+                    line,
+                    index: record.len(),
+                });
+            }
+            let invalid = |field| vcsv::Error::Veekun {
+                line,
+                field,
+                error: Box::new(vcsv::MiscError("Invalid snapshot value.")),
+            };
+            let pokemon_id = u16::from_le_bytes([record[0], record[1]]);
+            let pokemon_id = PokemonId::from_veekun(pokemon_id)
+                .ok_or_else(|| invalid(0))?;
+            let version_group = VersionGroup::from_veekun(record[2])
+                .ok_or_else(|| invalid(1))?;
+            let move_id = MoveId::from_veekun(
+                u16::from_le_bytes([record[3], record[4]]))
+                .ok_or_else(|| invalid(2))?;
+            let learn_method = LearnMethod::from_veekun(record[5])
+                .ok_or_else(|| invalid(3))?;
+            let level = record[6];
+            let pokemon_move = PokemonMove { move_id, learn_method, level };
+            table[pokemon_id].entry(version_group)
+                .or_insert(Vec::new()).push(pokemon_move);
+        }
+        Ok(table)
     }
 }
 
@@ -324,11 +580,43 @@ impl std::ops::IndexMut<PokemonId> for PokemonMoveTable {
     }
 }
 
+impl<'a> IntoIterator for &'a PokemonMoveTable {
+    type Item = (PokemonId, &'a HashMap<VersionGroup, Vec<PokemonMove>>);
+    type IntoIter = LearnsetIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the learnsets of a `PokemonMoveTable`, with their IDs.
+#[derive(Clone)]
+pub struct LearnsetIter<'a>(
+    std::iter::Enumerate<
+        std::slice::Iter<'a, HashMap<VersionGroup, Vec<PokemonMove>>>
+    >,
+);
+
+impl<'a> Iterator for LearnsetIter<'a> {
+    type Item = (PokemonId, &'a HashMap<VersionGroup, Vec<PokemonMove>>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(i, m)| (PokemonId(i as u16), m))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for LearnsetIter<'a> {}
+
 /// The number of stats that exist out of battle (all but accuracy and evasion).
 pub const PERMANENT_STATS: usize = 6;
 
 /// A Pokémon's base permanent stats.
 #[derive(Copy, Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BaseStats(pub [u8; PERMANENT_STATS]);
 
 impl std::ops::Index<Stat> for BaseStats {
@@ -345,17 +633,23 @@ impl std::ops::IndexMut<Stat> for BaseStats {
     }
 }
 
-struct StatTable([BaseStats; POKEMON_COUNT]);
+struct StatTable {
+    base: [BaseStats; POKEMON_COUNT],
+    effort: [Spread<u8>; POKEMON_COUNT],
+}
 
 impl StatTable {
-    fn new() -> Self {
-        StatTable::from_csv_data(vdata::STATS).unwrap()
+    fn from_source(source: &DataSource) -> Result<Self, LoadError> {
+        source.load(vdata::files::STATS)
     }
 }
 
 impl Default for StatTable {
     fn default() -> Self {
-        StatTable([Default::default(); POKEMON_COUNT])
+        StatTable {
+            base: [Default::default(); POKEMON_COUNT],
+            effort: [Default::default(); POKEMON_COUNT],
+        }
     }
 }
 
@@ -368,7 +662,9 @@ impl vcsv::FromCsvIncremental for StatTable {
         let id: PokemonId = vcsv::from_field(&record, 0)?;
         let stat = vcsv::from_field(&record, 1)?;
         let base = vcsv::from_field(&record, 2)?;
+        let effort = vcsv::from_field(&record, 3)?;
         self[id][stat] = base;
+        self.effort[id.0 as usize][stat] = effort;
         Ok(())
     }
 }
@@ -377,21 +673,21 @@ impl std::ops::Index<PokemonId> for StatTable {
     type Output = BaseStats;
 
     fn index(&self, index: PokemonId) -> &BaseStats {
-        self.0.index(index.0 as usize)
+        self.base.index(index.0 as usize)
     }
 }
 
 impl std::ops::IndexMut<PokemonId> for StatTable {
     fn index_mut(&mut self, index: PokemonId) -> &mut BaseStats {
-        self.0.index_mut(index.0 as usize)
+        self.base.index_mut(index.0 as usize)
     }
 }
 
 struct TypeTable([[Option<Type>; 2]; POKEMON_COUNT]);
 
 impl TypeTable {
-    fn new() -> Self {
-        TypeTable::from_csv_data(vdata::TYPES).unwrap()
+    fn from_source(source: &DataSource) -> Result<Self, LoadError> {
+        source.load(vdata::files::TYPES)
     }
 }
 
@@ -410,7 +706,7 @@ impl vcsv::FromCsvIncremental for TypeTable {
         let id: PokemonId = vcsv::from_field(&record, 0)?;
         let typ = vcsv::from_field(&record, 1)?;
         let slot: usize = vcsv::from_field(&record, 2)?;
-        if slot < 1 && slot > 2 {
+        if !(1..=2).contains(&slot) {
             return Err(vcsv::Error::Veekun {
                 line: vcsv::get_line(&record),
                 field: 2,
@@ -460,6 +756,7 @@ impl FromVeekun for SpeciesId {
 }
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Pokemon {
     pub id: PokemonId,
     pub abilities: OneOrTwo<Ability>,
@@ -467,9 +764,37 @@ pub struct Pokemon {
     pub forms: Vec<Form>,
     pub moves: HashMap<VersionGroup, Vec<PokemonMove>>,
     pub stats: BaseStats,
+    /// Height in decimetres.
+    pub height: u16,
+    /// Weight in hectograms.
+    pub weight: u16,
+    /// The base experience gained for defeating the Pokémon.
+    pub base_experience: u16,
+    /// The effort values gained for defeating the Pokémon.
+    pub ev_yield: Spread<u8>,
     pub types: OneOrTwo<Type>,
 }
 
+impl Pokemon {
+    /// The efficacy of each damage type against the Pokémon's types.
+    pub fn defensive_profile(
+        &self, efficacy: &EfficacyTable
+    ) -> DefensiveProfile {
+        efficacy.defensive_profile(self.types)
+    }
+
+    /// The Pokémon's types in the given generation, without any types
+    /// introduced later, as for Magnemite, which was purely Electric-type in
+    /// Generation I.
+    pub fn types_in(&self, generation: Generation) -> OneOrTwo<Type> {
+        let exists = |t: Type| t.generation().repr() <= generation.repr();
+        OneOrTwo::from_options([
+            Some(self.types.first()).filter(|&t| exists(t)),
+            self.types.second().filter(|&t| exists(t)),
+        ]).unwrap_or(self.types)
+    }
+}
+
 struct PokemonTable(Vec<Vec<Pokemon>>);
 
 impl Default for PokemonTable {
@@ -486,8 +811,14 @@ impl vcsv::FromCsvIncremental for PokemonTable {
     ) -> vcsv::Result<()> {
         let pokemon_id: PokemonId = vcsv::from_field(&record, 0)?;
         let species_id: SpeciesId = vcsv::from_field(&record, 1)?;
+        let height = vcsv::from_field(&record, 2)?;
+        let weight = vcsv::from_field(&record, 3)?;
+        let base_experience = vcsv::from_field(&record, 4)?;
         self[species_id].push(Pokemon {
             id: pokemon_id,
+            height,
+            weight,
+            base_experience,
             .. Default::default()
         });
         Ok(())
@@ -509,14 +840,18 @@ impl std::ops::IndexMut<SpeciesId> for PokemonTable {
 }
 
 impl PokemonTable {
-    fn new() -> Self {
-        let mut table = PokemonTable::from_csv_data(vdata::POKEMON).unwrap();
-        table.set_abilities(&AbilityTable::new());
-        table.set_forms(&FormTable::new());
-        table.set_moves(&PokemonMoveTable::new());
-        table.set_types(&TypeTable::new());
-        table.set_stats(&StatTable::new());
-        table
+    fn from_source(
+        source: &DataSource, with_moves: bool
+    ) -> Result<Self, LoadError> {
+        let mut table: Self = source.load(vdata::files::POKEMON)?;
+        table.set_abilities(&AbilityTable::from_source(source)?);
+        table.set_forms(&FormTable::from_source(source)?);
+        if with_moves {
+            table.set_moves(PokemonMoveTable::from_source(source)?);
+        }
+        table.set_types(&TypeTable::from_source(source)?);
+        table.set_stats(&StatTable::from_source(source)?);
+        Ok(table)
     }
 
     fn set_abilities(&mut self, ability_table: &AbilityTable) {
@@ -538,10 +873,10 @@ impl PokemonTable {
         }
     }
 
-    fn set_moves(&mut self, move_table: &PokemonMoveTable) {
+    fn set_moves(&mut self, mut move_table: PokemonMoveTable) {
         for species in self.0.iter_mut() {
-            for mut pokemon in species {
-                pokemon.moves = move_table[pokemon.id].clone();
+            for pokemon in species {
+                pokemon.moves = std::mem::take(&mut move_table[pokemon.id]);
             }
         }
     }
@@ -559,6 +894,7 @@ impl PokemonTable {
         for species in self.0.iter_mut() {
             for mut pokemon in species {
                 pokemon.stats = stat_table[pokemon.id];
+                pokemon.ev_yield = stat_table.effort[pokemon.id.0 as usize];
             }
         }
     }
@@ -567,8 +903,8 @@ impl PokemonTable {
 struct EggGroupTable(Vec<Vec<EggGroup>>);
 
 impl EggGroupTable {
-    fn new() -> Self {
-        EggGroupTable::from_csv_data(vdata::EGG_GROUPS).unwrap()
+    fn from_source(source: &DataSource) -> Result<Self, LoadError> {
+        source.load(vdata::files::EGG_GROUPS)
     }
 }
 
@@ -605,22 +941,44 @@ impl std::ops::IndexMut<SpeciesId> for EggGroupTable {
     }
 }
 
+/// One way a species evolves from its pre-evolution, and its conditions.
+///
+/// Conditions that are `None` (or the defaults, for `level` and `gender`) do
+/// not apply.
 #[derive(Copy, Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EvolvesFrom {
     pub from_id: SpeciesId,
     pub trigger: EvolutionTrigger,
+    /// The item to use on the Pokémon.
+    pub trigger_item: Option<ItemId>,
+    /// The minimum level, or 0.
     pub level: u8,
+    /// The required gender, or `Genderless` for any.
     pub gender: Gender,
-    pub move_id: MoveId,
+    /// The Veekun ID of the location to level up in.
+    pub location: Option<u16>,
+    /// The item the Pokémon must hold.
+    pub held_item: Option<ItemId>,
+    pub time_of_day: Option<TimeOfDay>,
+    /// A move the Pokémon must know.
+    pub move_id: Option<MoveId>,
+    pub min_happiness: Option<u8>,
+    pub min_beauty: Option<u8>,
+    /// The required sign of Attack minus Defense: 1, 0, or -1.
     pub relative_physical_stats: Option<i8>,
+    /// A species that must be in the party.
+    pub party_species: Option<SpeciesId>,
+    /// The species the Pokémon must be traded for.
+    pub trade_species: Option<SpeciesId>,
 }
 
 #[derive(Default)]
-struct EvolutionTable(HashMap<SpeciesId, EvolvesFrom>);
+struct EvolutionTable(HashMap<SpeciesId, Vec<EvolvesFrom>>);
 
 impl EvolutionTable {
-    fn new() -> Self {
-        EvolutionTable::from_csv_data(vdata::EVOLUTION).unwrap()
+    fn from_source(source: &DataSource) -> Result<Self, LoadError> {
+        source.load(vdata::files::EVOLUTION)
     }
 }
 
@@ -632,31 +990,58 @@ impl vcsv::FromCsvIncremental for EvolutionTable {
     ) -> vcsv::Result<()> {
         let species_id = vcsv::from_field(&record, 1)?;
         let trigger = vcsv::from_field(&record, 2)?;
+        let trigger_item: VeekunOption<_> = vcsv::from_field(&record, 3)?;
         let level = vcsv::from_option_field(&record, 4, 0)?;
         let gender = vcsv::from_option_field(&record, 5, Gender::Genderless)?;
-        let move_id = vcsv::from_option_field(&record, 9, Default::default())?;
+        let location: VeekunOption<_> = vcsv::from_field(&record, 6)?;
+        let held_item: VeekunOption<_> = vcsv::from_field(&record, 7)?;
+        let time: VeekunOption<VeekunString> = vcsv::from_field(&record, 8)?;
+        let time_of_day = match time.0.as_ref().map(|t| t.as_str()) {
+            None => None,
+            Some("day") => Some(TimeOfDay::Day),
+            Some("night") => Some(TimeOfDay::Night),
+            Some(_) => return Err(vcsv::Error::Veekun {
+                line: vcsv::get_line(&record),
+                field: 8,
+                error: Box::new(vcsv::MiscError("Invalid time of day.")),
+            }),
+        };
+        let move_id: VeekunOption<_> = vcsv::from_field(&record, 9)?;
+        let min_happiness: VeekunOption<_> = vcsv::from_field(&record, 10)?;
+        let min_beauty: VeekunOption<_> = vcsv::from_field(&record, 11)?;
         let rps: VeekunOption<_> = vcsv::from_field(&record, 12)?;
-        self.0.insert(species_id, EvolvesFrom {
+        let party_species: VeekunOption<_> = vcsv::from_field(&record, 13)?;
+        let trade_species: VeekunOption<_> = vcsv::from_field(&record, 14)?;
+        self.0.entry(species_id).or_default().push(EvolvesFrom {
             from_id: Default::default(),
             trigger,
+            trigger_item: trigger_item.into(),
             level,
             gender,
-            move_id,
+            location: location.into(),
+            held_item: held_item.into(),
+            time_of_day,
+            move_id: move_id.into(),
+            min_happiness: min_happiness.into(),
+            min_beauty: min_beauty.into(),
             relative_physical_stats: rps.into(),
+            party_species: party_species.into(),
+            trade_species: trade_species.into(),
         });
         Ok(())
     }
 }
 
 impl std::ops::Index<SpeciesId> for EvolutionTable {
-    type Output = EvolvesFrom;
+    type Output = [EvolvesFrom];
 
-    fn index(&self, index: SpeciesId) -> &EvolvesFrom {
-        self.0.index(&index)
+    fn index(&self, index: SpeciesId) -> &[EvolvesFrom] {
+        self.0.get(&index).map_or(&[], |v| v.as_slice())
     }
 }
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Species {
     pub id: SpeciesId,
     pub name: String,
@@ -664,15 +1049,69 @@ pub struct Species {
     pub gender_rate: i8,
     pub pokemon: Vec<Pokemon>,
     pub egg_groups: OneOrTwo<EggGroup>,
-    pub evolves_from: Option<EvolvesFrom>,
+    /// The ways the species evolves from its pre-evolution, if it has one.
+    /// All have the same `from_id`.
+    pub evolves_from: Vec<EvolvesFrom>,
+    /// The Veekun ID of the species' evolution chain, shared by its whole
+    /// family. See `evolution::EvolutionGraph`.
+    pub evolution_chain: u16,
+    pub growth_rate: GrowthRate,
+    /// How easily the species is caught, from 3 (hardest) to 255.
+    pub capture_rate: u8,
+    pub color: Color,
+    pub shape: Shape,
+    pub habitat: Option<Habitat>,
+    /// The initial happiness of a caught or hatched Pokémon.
+    pub base_happiness: u8,
+    /// Whether the species is a baby, which cannot breed.
+    pub is_baby: bool,
+    /// The number of egg cycles to hatch an egg. See `hatch_steps`.
+    pub hatch_counter: u8,
+    /// Whether males and females look different.
+    pub has_gender_differences: bool,
+    /// Whether the Pokémon can switch between forms outside battle.
+    pub forms_switchable: bool,
+    /// The sort order of the species, grouping evolutionary families.
+    pub order: u16,
+}
+
+impl Species {
+    /// The species this species evolves from, if any.
+    pub fn pre_evolution(&self) -> Option<SpeciesId> {
+        self.evolves_from.first().map(|e| e.from_id)
+    }
+
+    /// The number of steps to hatch an egg of the species in a generation.
+    ///
+    /// Each egg cycle is 256 steps in Generations II and III, and 255 steps
+    /// from Generation IV onward. Eggs were introduced in Generation II, so
+    /// this is `None` for Generation I.
+    pub fn hatch_steps(&self, generation: Generation) -> Option<u32> {
+        let cycle = match generation {
+            Generation::I => return None,
+            Generation::II | Generation::III => 256,
+            _ => 255,
+        };
+        Some(cycle * (self.hatch_counter as u32 + 1))
+    }
 }
 
-pub struct SpeciesTable(Vec<Species>);
+/// Wrapper of a `Vec` for all species.
+///
+/// A species' index is its Veekun ID minus 1. After renaming species through
+/// `IndexMut`, call `reindex`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpeciesTable(
+    Vec<Species>,
+    #[cfg_attr(feature = "serde", serde(skip))] NameIndex<SpeciesId>,
+);
 
 impl Default for SpeciesTable {
     fn default() -> Self {
-        SpeciesTable(repeat(Default::default())
-                .take(SPECIES_COUNT).collect::<Vec<_>>())
+        SpeciesTable(
+            repeat(Default::default()).take(SPECIES_COUNT).collect::<Vec<_>>(),
+            Default::default(),
+        )
     }
 }
 
@@ -686,15 +1125,39 @@ impl vcsv::FromCsvIncremental for SpeciesTable {
         let identifier: VeekunString = vcsv::from_field(&record, 1)?;
         let generation = vcsv::from_field(&record, 2)?;
         let gender_rate = vcsv::from_field(&record, 8)?;
+        let evolution_chain = vcsv::from_field(&record, 4)?;
+        let color = vcsv::from_field(&record, 5)?;
+        let shape = vcsv::from_field(&record, 6)?;
+        let habitat: VeekunOption<_> = vcsv::from_field(&record, 7)?;
+        let capture_rate = vcsv::from_field(&record, 9)?;
+        let base_happiness = vcsv::from_field(&record, 10)?;
+        let is_baby: u8 = vcsv::from_field(&record, 11)?;
+        let hatch_counter = vcsv::from_field(&record, 12)?;
+        let has_gender_differences: u8 = vcsv::from_field(&record, 13)?;
+        let growth_rate = vcsv::from_field(&record, 14)?;
+        let forms_switchable: u8 = vcsv::from_field(&record, 15)?;
+        let order = vcsv::from_field(&record, 16)?;
         self[id].id = id;
         self[id].name = to_pascal_case(identifier.as_str());
         self[id].generation = generation;
         self[id].gender_rate = gender_rate;
+        self[id].capture_rate = capture_rate;
+        self[id].growth_rate = growth_rate;
+        self[id].evolution_chain = evolution_chain;
+        self[id].color = color;
+        self[id].shape = shape;
+        self[id].habitat = habitat.into();
+        self[id].base_happiness = base_happiness;
+        self[id].is_baby = is_baby != 0;
+        self[id].hatch_counter = hatch_counter;
+        self[id].has_gender_differences = has_gender_differences != 0;
+        self[id].forms_switchable = forms_switchable != 0;
+        self[id].order = order;
         if let VeekunOption(Some(from_id)) = vcsv::from_field(&record, 3)? {
-            self[id].evolves_from = Some(EvolvesFrom {
+            self[id].evolves_from = vec![EvolvesFrom {
                 from_id,
                 .. Default::default()
-            });
+            }];
         }
         Ok(())
     }
@@ -714,19 +1177,108 @@ impl std::ops::IndexMut<SpeciesId> for SpeciesTable {
     }
 }
 
+impl<'a> IntoIterator for &'a SpeciesTable {
+    type Item = &'a Species;
+    type IntoIter = std::slice::Iter<'a, Species>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl SpeciesTable {
+    /// Create a species table from the included Veekun CSV data.
     pub fn new() -> Self {
-        let mut table = SpeciesTable::from_csv_data(vdata::SPECIES).unwrap();
-        table.set_pokemon(&PokemonTable::new());
-        table.set_egg_groups(&EggGroupTable::new());
-        table.set_evolutions(&EvolutionTable::new());
-        table
+        Self::try_new().unwrap()
+    }
+
+    /// Like `new`, but returns an error instead of panicking.
+    pub fn try_new() -> Result<Self, LoadError> {
+        Self::from_source(&DataSource::Embedded)
+    }
+
+    /// Create the table from the CSV files in the given source.
+    pub fn from_source(source: &DataSource) -> Result<Self, LoadError> {
+        Self::load(source, true)
+    }
+
+    /// Like `from_source`, but leaves `Pokemon.moves` empty.
+    ///
+    /// The learnsets are by far the largest part of the data, so this is much
+    /// faster. They can be loaded separately with `PokemonMoveTable`.
+    pub fn from_source_without_moves(
+        source: &DataSource
+    ) -> Result<Self, LoadError> {
+        Self::load(source, false)
+    }
+
+    fn load(source: &DataSource, with_moves: bool) -> Result<Self, LoadError> {
+        let mut table: Self = source.load(vdata::files::SPECIES)?;
+        table.set_pokemon(PokemonTable::from_source(source, with_moves)?);
+        table.set_egg_groups(&EggGroupTable::from_source(source)?);
+        table.set_evolutions(&EvolutionTable::from_source(source)?);
+        table.1.build(table.names());
+        Ok(table)
+    }
+
+    /// Iterate over all species in ID order.
+    pub fn iter(&self) -> std::slice::Iter<'_, Species> {
+        self.0.iter()
+    }
+
+    /// The number of species.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the table has no species.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
     }
 
-    fn set_pokemon(&mut self, pokemon_table: &PokemonTable) {
+    /// Get a species by ID, or `None` if the ID is out of range.
+    pub fn get(&self, id: SpeciesId) -> Option<&Species> {
+        self.0.get(id.0 as usize)
+    }
+
+    /// Iterate over the Pokémon of all species, in species order.
+    pub fn pokemon(&self) -> impl Iterator<Item = &Pokemon> {
+        self.iter().flat_map(|s| s.pokemon.iter())
+    }
+
+    /// Find a species by name, ignoring case and punctuation (see
+    /// `veekun::names_match`).
+    pub fn by_name(&self, name: &str) -> Option<&Species> {
+        self.1.get(name, || self.names()).map(|id| &self[id])
+    }
+
+    /// Rebuild the name index used by `by_name`, e.g. after renaming species.
+    pub fn reindex(&mut self) {
+        self.1.clear();
+        self.1.build(self.names());
+    }
+
+    fn names(&self) -> impl Iterator<Item = (&str, SpeciesId)> {
+        self.iter().map(|s| (s.name.as_str(), s.id))
+    }
+
+    /// Find a Pokémon by ID across all species.
+    pub fn find_pokemon(&self, id: PokemonId) -> Option<&Pokemon> {
+        self.pokemon().find(|p| p.id == id)
+    }
+
+    /// Find a Pokémon by ID across all species, for mutation.
+    pub(crate) fn find_pokemon_mut(
+        &mut self, id: PokemonId
+    ) -> Option<&mut Pokemon> {
+        self.0.iter_mut().flat_map(|s| s.pokemon.iter_mut())
+            .find(|p| p.id == id)
+    }
+
+    fn set_pokemon(&mut self, mut pokemon_table: PokemonTable) {
         for i in 0..SPECIES_COUNT {
             let id = SpeciesId(i as u16);
-            self[id].pokemon = pokemon_table[id].clone();
+            self[id].pokemon = std::mem::take(&mut pokemon_table[id]);
         }
     }
 
@@ -744,11 +1296,16 @@ impl SpeciesTable {
     fn set_evolutions(&mut self, evolution_table: &EvolutionTable) {
         for i in 0..SPECIES_COUNT {
             let id = SpeciesId(i as u16);
-            self[id].evolves_from
-                = self[id].evolves_from.map(|e| EvolvesFrom {
-                    from_id: e.from_id,
-                    .. evolution_table[id]
-                });
+            // Species without evolution rows keep the plain link to their
+            // pre-evolution.
+            let evolutions = &evolution_table[id];
+            if evolutions.is_empty() {
+                continue;
+            }
+            if let Some(from_id) = self[id].pre_evolution() {
+                self[id].evolves_from = evolutions.iter()
+                    .map(|e| EvolvesFrom { from_id, .. *e }).collect();
+            }
         }
     }
 }
@@ -0,0 +1,98 @@
+//! Fuzzy name search across moves, items, species, and abilities.
+//!
+//! Names are compared with case and punctuation ignored (see
+//! `veekun::name_key`), so `"thunder bolt"` is an exact match for
+//! Thunderbolt. Misspellings are resolved by edit distance, and names that
+//! start with the query (or that the query starts with, as in
+//! `"garchomp-mega"`) are always included as candidates.
+
+use std::cmp::min;
+use crate::Ability;
+use crate::Enum;
+use crate::items::ItemId;
+use crate::moves::MoveId;
+use crate::pokemon::SpeciesId;
+use crate::Pokedex;
+use veekun::name_key;
+
+/// An entity found by a search, along with its kind.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Entity {
+    Ability(Ability),
+    Item(ItemId),
+    Move(MoveId),
+    Species(SpeciesId),
+}
+
+/// A possible match for a search query.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Candidate {
+    /// The matched entity.
+    pub entity: Entity,
+    /// The name of the matched entity.
+    pub name: String,
+    /// The edit distance between the query and the name, ignoring case and
+    /// punctuation. Zero for an exact match.
+    pub distance: usize,
+    /// Whether either of the query and the name is a prefix of the other.
+    pub prefix: bool,
+}
+
+/// Search all moves, items, species, and abilities for names resembling
+/// `query`.
+///
+/// Returns at most `limit` candidates, best first: candidates are ranked by
+/// edit distance, and then prefix matches are preferred. Names further from
+/// the query than a third of its length are excluded unless they are prefix
+/// matches.
+pub fn search(dex: &Pokedex, query: &str, limit: usize) -> Vec<Candidate> {
+    let query: Vec<char> = name_key(query).collect();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let max_distance = (query.len() / 3).max(1);
+    let abilities = Ability::VALUES.iter()
+        .map(|&a| (Entity::Ability(a), format!("{:?}", a)));
+    let items = dex.items.iter()
+        .map(|i| (Entity::Item(i.id), i.name.clone()));
+    let moves = dex.moves.iter()
+        .map(|m| (Entity::Move(m.id), m.name.clone()));
+    let species = dex.species.iter()
+        .map(|s| (Entity::Species(s.id), s.name.clone()));
+    let mut candidates: Vec<Candidate> = abilities.chain(items).chain(moves)
+        .chain(species)
+        .filter_map(|(entity, name)| {
+            let key: Vec<char> = name_key(&name).collect();
+            let prefix = key.starts_with(&query) || query.starts_with(&key);
+            let distance = edit_distance(&query, &key);
+            if distance <= max_distance || (prefix && !key.is_empty()) {
+                Some(Candidate { entity, name, distance, prefix })
+            } else {
+                None
+            }
+        })
+        .collect();
+    candidates.sort_by(|a, b| a.distance.cmp(&b.distance)
+        .then(b.prefix.cmp(&a.prefix))
+        .then_with(|| a.name.cmp(&b.name)));
+    candidates.truncate(limit);
+    candidates
+}
+
+/// The Levenshtein distance between two strings: the number of single
+/// character insertions, deletions, and substitutions to turn one into the
+/// other.
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + if ca == cb { 0 } else { 1 };
+            current[j + 1] = min(substitution,
+                                 min(previous[j + 1], current[j]) + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
@@ -0,0 +1,213 @@
+//! Serde support, enabled by the `serde` feature.
+//!
+//! Most types derive `Serialize` and `Deserialize` where they are defined.
+//! This module has the implementations that need special representations:
+//!
+//! - Enums are represented by their Veekun-style `kebab-case` identifier
+//!   (e.g. `"special-attack"`).
+//! - IDs are represented by their Veekun number (e.g. `MoveId(0)` is `1`).
+//!   Unset IDs (the `Default`) are represented by `0`.
+//! - Flags are represented by a list of `kebab-case` flag names.
+//! - `OneOrTwo` is represented by a list of one or two elements.
+
+use std::fmt::Debug;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::{Error, Unexpected};
+use serde::ser::SerializeSeq;
+use veekun::to_kebab_case;
+use crate::Enum;
+use crate::FromVeekun;
+use crate::items;
+use crate::items::ItemId;
+use crate::moves;
+use crate::moves::MoveId;
+use crate::pokemon::{OneOrTwo, PokemonId, SpeciesId};
+use crate::versions;
+
+fn enum_identifier<T: Enum + Debug>(value: T) -> String {
+    to_kebab_case(&format!("{:?}", value))
+}
+
+fn serialize_enum<T: Enum + Debug, S: Serializer>(
+    value: T, serializer: S
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&enum_identifier(value))
+}
+
+fn deserialize_enum<'de, T: Enum + Debug, D: Deserializer<'de>>(
+    deserializer: D
+) -> Result<T, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    T::VALUES.iter().find(|v| enum_identifier(**v) == s).cloned()
+        .ok_or_else(|| D::Error::invalid_value(
+            Unexpected::Str(&s), &"a kebab-case identifier"))
+}
+
+macro_rules! serde_enums {
+    ($($t:ty),* $(,)*) => { $(
+        impl Serialize for $t {
+            fn serialize<S: Serializer>(
+                &self, serializer: S
+            ) -> Result<S::Ok, S::Error> {
+                serialize_enum(*self, serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $t {
+            fn deserialize<D: Deserializer<'de>>(
+                deserializer: D
+            ) -> Result<Self, D::Error> {
+                deserialize_enum(deserializer)
+            }
+        }
+    )* }
+}
+
+serde_enums! {
+    crate::Ability,
+    crate::CombinedEfficacy,
+    crate::Efficacy,
+    crate::Nature,
+    crate::Stat,
+    crate::Type,
+    items::Category,
+    items::Flavor,
+    items::FlingEffect,
+    items::Pocket,
+    moves::Ailment,
+    moves::BattleStyle,
+    moves::Category,
+    moves::DamageClass,
+    moves::Effect,
+    moves::LearnMethod,
+    moves::Target,
+    crate::pokemon::Color,
+    crate::pokemon::EggGroup,
+    crate::pokemon::EvolutionTrigger,
+    crate::pokemon::Gender,
+    crate::pokemon::GrowthRate,
+    crate::pokemon::Habitat,
+    crate::pokemon::Shape,
+    crate::pokemon::TimeOfDay,
+    versions::Generation,
+    versions::Version,
+    versions::VersionGroup,
+}
+
+macro_rules! serde_ids {
+    ($($t:ident: $offset:expr),* $(,)*) => { $(
+        impl Serialize for $t {
+            fn serialize<S: Serializer>(
+                &self, serializer: S
+            ) -> Result<S::Ok, S::Error> {
+                serializer.serialize_u16(self.0 + $offset)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $t {
+            fn deserialize<D: Deserializer<'de>>(
+                deserializer: D
+            ) -> Result<Self, D::Error> {
+                let value = u16::deserialize(deserializer)?;
+                $t::from_veekun(value).ok_or_else(|| D::Error::invalid_value(
+                    Unexpected::Unsigned(value as u64), &"a Veekun ID"))
+            }
+        }
+    )* }
+}
+
+serde_ids! {
+    ItemId: 0,
+    MoveId: 1,
+    PokemonId: 1,
+    SpeciesId: 1,
+}
+
+macro_rules! serde_flags {
+    ($($t:ty { $($name:expr => $flag:expr),* $(,)* })*) => { $(
+        impl Serialize for $t {
+            fn serialize<S: Serializer>(
+                &self, serializer: S
+            ) -> Result<S::Ok, S::Error> {
+                let names = [$(($name, $flag)),*];
+                let set = names.iter().filter(|(_, f)| self.contains(*f));
+                let mut seq = serializer.serialize_seq(None)?;
+                for (name, _) in set {
+                    seq.serialize_element(name)?;
+                }
+                seq.end()
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $t {
+            fn deserialize<D: Deserializer<'de>>(
+                deserializer: D
+            ) -> Result<Self, D::Error> {
+                let names = [$(($name, $flag)),*];
+                let mut flags = Self::empty();
+                for s in Vec::<String>::deserialize(deserializer)? {
+                    match names.iter().find(|(name, _)| *name == s) {
+                        Some((_, flag)) => flags |= *flag,
+                        None => return Err(D::Error::invalid_value(
+                            Unexpected::Str(&s), &"a flag name")),
+                    }
+                }
+                Ok(flags)
+            }
+        }
+    )* }
+}
+
+serde_flags! {
+    items::Flags {
+        "countable" => items::Flags::COUNTABLE,
+        "consumable" => items::Flags::CONSUMABLE,
+        "usable-overworld" => items::Flags::USABLE_OVERWORLD,
+        "usable-in-battle" => items::Flags::USABLE_IN_BATTLE,
+        "holdable" => items::Flags::HOLDABLE,
+        "holdable-passive" => items::Flags::HOLDABLE_PASSIVE,
+        "holdable-active" => items::Flags::HOLDABLE_ACTIVE,
+        "underground" => items::Flags::UNDERGROUND,
+    }
+    moves::Flags {
+        "contact" => moves::Flags::CONTACT,
+        "charge" => moves::Flags::CHARGE,
+        "recharge" => moves::Flags::RECHARGE,
+        "protect" => moves::Flags::PROTECT,
+        "reflectable" => moves::Flags::REFLECTABLE,
+        "snatch" => moves::Flags::SNATCH,
+        "mirror" => moves::Flags::MIRROR,
+        "punch" => moves::Flags::PUNCH,
+        "sound" => moves::Flags::SOUND,
+        "gravity" => moves::Flags::GRAVITY,
+        "defrost" => moves::Flags::DEFROST,
+        "distance" => moves::Flags::DISTANCE,
+        "heal" => moves::Flags::HEAL,
+        "authentic" => moves::Flags::AUTHENTIC,
+    }
+}
+
+impl<T: Copy + Serialize> Serialize for OneOrTwo<T> {
+    fn serialize<S: Serializer>(
+        &self, serializer: S
+    ) -> Result<S::Ok, S::Error> {
+        match *self {
+            OneOrTwo::One(a) => [a].serialize(serializer),
+            OneOrTwo::Two(a, b) => [a, b].serialize(serializer),
+        }
+    }
+}
+
+impl<'de, T: Copy + Deserialize<'de>> Deserialize<'de> for OneOrTwo<T> {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D
+    ) -> Result<Self, D::Error> {
+        let elements = Vec::<T>::deserialize(deserializer)?;
+        match elements.as_slice() {
+            [a] => Ok(OneOrTwo::One(*a)),
+            [a, b] => Ok(OneOrTwo::Two(*a, *b)),
+            _ => Err(D::Error::invalid_length(
+                elements.len(), &"one or two elements")),
+        }
+    }
+}
@@ -0,0 +1,408 @@
+//! Calculation of a Pokémon's actual stats.
+//!
+//! > [*[From Bulbapedia:]*](https://bulbapedia.bulbagarden.net/wiki/Statistic)
+//! > Each individual Pokémon's stats are calculated using the following
+//! > formulas . . . [which] take into account the Pokémon's base stats,
+//! > individual values, effort values, level, and Nature.
+//!
+//! From Generation III onward, stats are determined by individual values
+//! (IVs), effort values (EVs), and nature; see `calculate`. Generations I and
+//! II instead use determinant values (DVs) and stat experience; see
+//! `calculate_retro`. `calculate_for` selects between them by generation.
+
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+use crate::Enum;
+use crate::items::ItemId;
+use crate::Nature;
+use crate::pokemon::{BaseStats, Pokemon, PERMANENT_STATS};
+use crate::Stat;
+use crate::versions::Generation;
+
+/// The maximum individual value of a stat.
+pub const MAX_IV: u8 = 31;
+
+/// The maximum effort value of a stat.
+pub const MAX_EV: u8 = 255;
+
+/// The maximum total of a Pokémon's effort values.
+pub const MAX_TOTAL_EVS: u16 = 510;
+
+/// The maximum level of a Pokémon.
+pub const MAX_LEVEL: u8 = 100;
+
+/// The maximum determinant value of a stat in Generations I and II.
+pub const MAX_DV: u8 = 15;
+
+/// A value for each permanent stat, such as IVs, EVs, or the actual stats.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Spread<T>(pub [T; PERMANENT_STATS]);
+
+impl<T> std::ops::Index<Stat> for Spread<T> {
+    type Output = T;
+
+    fn index(&self, index: Stat) -> &T {
+        &self.0[(index.repr() + 1) as usize]
+    }
+}
+
+impl<T> std::ops::IndexMut<Stat> for Spread<T> {
+    fn index_mut(&mut self, index: Stat) -> &mut T {
+        &mut self.0[(index.repr() + 1) as usize]
+    }
+}
+
+impl Spread<u8> {
+    /// The sum of the values, e.g. the total EVs.
+    pub fn total(&self) -> u16 {
+        self.0.iter().map(|&x| x as u16).sum()
+    }
+}
+
+/// The permanent stats, in the order of `Spread`.
+pub fn permanent_stats() -> impl Iterator<Item = Stat> {
+    Stat::VALUES.iter().cloned().take(PERMANENT_STATS)
+}
+
+/// An invalid input to a stat calculation.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum StatError {
+    /// The IV of the stat is greater than `MAX_IV`.
+    IV(Stat, u8),
+    /// The EVs total more than `MAX_TOTAL_EVS`.
+    TotalEVs(u16),
+    /// The level is 0 or greater than `MAX_LEVEL`.
+    Level(u8),
+    /// The DV of the stat is greater than `MAX_DV`.
+    DV(Stat, u8),
+    /// The training values are not used in the generation.
+    Generation(Generation),
+}
+
+impl Display for StatError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            StatError::IV(stat, iv)
+                => write!(f, "{:?} IV {} is greater than {}", stat, iv, MAX_IV),
+            StatError::TotalEVs(total)
+                => write!(f, "EVs total {}, more than {}", total,
+                          MAX_TOTAL_EVS),
+            StatError::Level(level)
+                => write!(f, "Level {} is not between 1 and {}", level,
+                          MAX_LEVEL),
+            StatError::DV(stat, dv)
+                => write!(f, "{:?} DV {} is greater than {}", stat, dv, MAX_DV),
+            StatError::Generation(generation)
+                => write!(f, "Wrong stat formula for Generation {:?}",
+                          generation),
+        }
+    }
+}
+
+impl StdError for StatError { }
+
+/// Calculate all the permanent stats of a Pokémon.
+///
+/// Returns an error if any IV, the EV total, or the level is out of range.
+pub fn calculate(
+    base: &BaseStats, ivs: &Spread<u8>, evs: &Spread<u8>, level: u8,
+    nature: Nature,
+) -> Result<Spread<u16>, StatError> {
+    check_level(level)?;
+    if evs.total() > MAX_TOTAL_EVS {
+        return Err(StatError::TotalEVs(evs.total()));
+    }
+    let mut stats = Spread::default();
+    for stat in permanent_stats() {
+        if ivs[stat] > MAX_IV {
+            return Err(StatError::IV(stat, ivs[stat]));
+        }
+        stats[stat] = calculate_stat(
+            stat, base[stat], ivs[stat], evs[stat], level, nature);
+    }
+    Ok(stats)
+}
+
+/// Calculate a single permanent stat, without checking the inputs.
+///
+/// A base HP of 1 (Shedinja) always results in 1 HP.
+pub fn calculate_stat(
+    stat: Stat, base: u8, iv: u8, ev: u8, level: u8, nature: Nature
+) -> u16 {
+    let core = (2 * base as u32 + iv as u32 + ev as u32 / 4) * level as u32
+        / 100;
+    if stat == Stat::HP {
+        if base == 1 {
+            return 1;
+        }
+        return (core + level as u32 + 10) as u16;
+    }
+    ((core + 5) * nature_percent(nature, stat) / 100) as u16
+}
+
+/// The percentage a nature multiplies a stat by: 110, 90, or 100.
+pub fn nature_percent(nature: Nature, stat: Stat) -> u32 {
+    if nature.increased() == nature.decreased() {
+        100
+    } else if nature.increased() == Some(stat) {
+        110
+    } else if nature.decreased() == Some(stat) {
+        90
+    } else {
+        100
+    }
+}
+
+/// Find the IVs that could result in an observed stat.
+///
+/// Returns `None` if no IV produces the stat, e.g. because the EVs or level
+/// are wrong.
+pub fn iv_range(
+    stat: Stat, base: u8, observed: u16, ev: u8, level: u8, nature: Nature
+) -> Option<RangeInclusive<u8>> {
+    let mut matching = (0..=MAX_IV)
+        .filter(|&iv| calculate_stat(stat, base, iv, ev, level, nature)
+                == observed);
+    let min = matching.next()?;
+    let max = matching.next_back().unwrap_or(min);
+    Some(min..=max)
+}
+
+/// Find the IVs that could result in each of the observed stats. See
+/// `iv_range`.
+pub fn iv_ranges(
+    base: &BaseStats, observed: &Spread<u16>, evs: &Spread<u8>, level: u8,
+    nature: Nature,
+) -> Result<Spread<Option<RangeInclusive<u8>>>, StatError> {
+    check_level(level)?;
+    let mut ranges = Spread::default();
+    for stat in permanent_stats() {
+        ranges[stat] = iv_range(
+            stat, base[stat], observed[stat], evs[stat], level, nature);
+    }
+    Ok(ranges)
+}
+
+/// The item ID of the Macho Brace, which doubles EVs gained.
+pub const MACHO_BRACE: ItemId = ItemId(192);
+
+/// The item IDs of the Power items, each of which adds 4 EVs of a stat to
+/// those gained: Power Bracer, Power Belt, Power Lens, Power Band, Power
+/// Anklet, and Power Weight.
+pub const POWER_ITEMS: [(ItemId, Stat); 6] = [
+    (ItemId(266), Stat::Attack),
+    (ItemId(267), Stat::Defense),
+    (ItemId(268), Stat::SpecialAttack),
+    (ItemId(269), Stat::SpecialDefense),
+    (ItemId(270), Stat::Speed),
+    (ItemId(271), Stat::HP),
+];
+
+/// Accumulates the EVs a Pokémon gains from defeating other Pokémon.
+///
+/// > [*[From
+/// > Bulbapedia:]*](https://bulbapedia.bulbagarden.net/wiki/Effort_values)
+/// > When a Pokémon is defeated, the Pokémon that participated in the battle
+/// > will gain a set amount of effort values. . . . A Pokémon can only have a
+/// > total of 510 effort values, with a maximum of 255 in any stat.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct EvTrainer {
+    /// The EVs gained so far.
+    pub evs: Spread<u8>,
+    /// The item held by the Pokémon being trained.
+    pub held_item: Option<ItemId>,
+    /// Whether the Pokémon being trained has Pokérus, doubling EVs gained.
+    pub pokerus: bool,
+}
+
+impl EvTrainer {
+    /// Gain the EVs for defeating a Pokémon, returning those actually gained
+    /// after the caps.
+    pub fn defeat(&mut self, defeated: &Pokemon) -> Spread<u8> {
+        self.gain(&defeated.ev_yield)
+    }
+
+    /// Gain the EVs for defeating each of a sequence of Pokémon in order,
+    /// returning the total actually gained.
+    pub fn defeat_all<'a, I>(&mut self, defeated: I) -> Spread<u8>
+        where I: IntoIterator<Item = &'a Pokemon>
+    {
+        let mut total = Spread::default();
+        for pokemon in defeated {
+            let gained = self.defeat(pokemon);
+            for stat in permanent_stats() {
+                total[stat] += gained[stat];
+            }
+        }
+        total
+    }
+
+    /// Gain EVs from an EV yield, applying the held item and Pokérus, and
+    /// returning the EVs actually gained after the caps.
+    pub fn gain(&mut self, ev_yield: &Spread<u8>) -> Spread<u8> {
+        let mut gained = Spread::default();
+        for stat in permanent_stats() {
+            let mut amount = ev_yield[stat] as u16;
+            if POWER_ITEMS.iter()
+                .any(|&(item, s)| self.held_item == Some(item) && s == stat)
+            {
+                amount += 4;
+            }
+            if self.held_item == Some(MACHO_BRACE) {
+                amount *= 2;
+            }
+            if self.pokerus {
+                amount *= 2;
+            }
+            let stat_room = (MAX_EV - self.evs[stat]) as u16;
+            let total_room = MAX_TOTAL_EVS.saturating_sub(self.evs.total());
+            let amount = amount.min(stat_room).min(total_room) as u8;
+            self.evs[stat] += amount;
+            gained[stat] = amount;
+        }
+        gained
+    }
+}
+
+/// Determinant values, the Generation I and II equivalent of IVs.
+///
+/// > [*[From
+/// > Bulbapedia:]*](https://bulbapedia.bulbagarden.net/wiki/Individual_values)
+/// > In Generations I and II, IVs, known as "determinant values" (DVs) . . .
+/// > range from 0 to 15. . . . Instead of being individually determined, the
+/// > HP IV is calculated based on the other IVs . . . [and] the Special IV is
+/// > shared between Special Attack and Special Defense.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DVs {
+    pub attack: u8,
+    pub defense: u8,
+    pub speed: u8,
+    pub special: u8,
+}
+
+impl DVs {
+    /// The HP DV, made of the lowest bit of each other DV.
+    pub fn hp(&self) -> u8 {
+        (self.attack & 1) << 3 | (self.defense & 1) << 2
+            | (self.speed & 1) << 1 | (self.special & 1)
+    }
+
+    /// The DV of each stat, with the Special DV for both special stats.
+    pub fn spread(&self) -> Spread<u8> {
+        Spread([self.hp(), self.attack, self.defense, self.speed,
+                self.special, self.special])
+    }
+}
+
+/// Stat experience, the Generation I and II equivalent of EVs.
+///
+/// Each stat has its own experience, except that Special Attack and Special
+/// Defense share the Special experience.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StatExp {
+    pub hp: u16,
+    pub attack: u16,
+    pub defense: u16,
+    pub speed: u16,
+    pub special: u16,
+}
+
+impl StatExp {
+    /// The experience of each stat, with the Special experience for both
+    /// special stats.
+    pub fn spread(&self) -> Spread<u16> {
+        Spread([self.hp, self.attack, self.defense, self.speed,
+                self.special, self.special])
+    }
+}
+
+/// Calculate all the permanent stats of a Pokémon in Generation I or II.
+///
+/// The base stats are those of the included data, from Generation V. In
+/// Generation I, the unified Special stat is calculated from the Special
+/// Attack base stat and returned as both Special Attack and Special Defense.
+///
+/// Returns an error if any DV or the level is out of range, or the generation
+/// is not I or II.
+pub fn calculate_retro(
+    generation: Generation, base: &BaseStats, dvs: &DVs, stat_exp: &StatExp,
+    level: u8,
+) -> Result<Spread<u16>, StatError> {
+    check_level(level)?;
+    if generation.repr() > Generation::II.repr() {
+        return Err(StatError::Generation(generation));
+    }
+    let dvs = dvs.spread();
+    let stat_exp = stat_exp.spread();
+    let mut stats = Spread::default();
+    for stat in permanent_stats() {
+        if dvs[stat] > MAX_DV {
+            return Err(StatError::DV(stat, dvs[stat]));
+        }
+        let base = match (generation, stat) {
+            (Generation::I, Stat::SpecialDefense) => base[Stat::SpecialAttack],
+            _ => base[stat],
+        };
+        stats[stat] = calculate_retro_stat(
+            stat, base, dvs[stat], stat_exp[stat], level);
+    }
+    Ok(stats)
+}
+
+/// Calculate a single permanent stat in Generation I or II, without checking
+/// the inputs.
+pub fn calculate_retro_stat(
+    stat: Stat, base: u8, dv: u8, stat_exp: u16, level: u8
+) -> u16 {
+    // The square root is rounded up and capped at 255.
+    let exp_root = match stat_exp {
+        0 => 0,
+        x => ((x - 1) as f64).sqrt() as u32 + 1,
+    };
+    let exp_bonus = exp_root.min(255) / 4;
+    let core = ((base as u32 + dv as u32) * 2 + exp_bonus) * level as u32
+        / 100;
+    match stat {
+        Stat::HP => (core + level as u32 + 10) as u16,
+        _ => (core + 5) as u16,
+    }
+}
+
+/// The values that determine a Pokémon's stats besides its species and level.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Training {
+    /// IVs, EVs, and nature, used from Generation III onward.
+    Modern { ivs: Spread<u8>, evs: Spread<u8>, nature: Nature },
+    /// DVs and stat experience, used in Generations I and II.
+    Retro { dvs: DVs, stat_exp: StatExp },
+}
+
+/// Calculate all the permanent stats of a Pokémon with the formula of the
+/// given generation.
+///
+/// Returns an error if the kind of training does not match the generation, or
+/// any of its values are out of range.
+pub fn calculate_for(
+    generation: Generation, base: &BaseStats, training: &Training, level: u8,
+) -> Result<Spread<u16>, StatError> {
+    let modern = generation.repr() >= Generation::III.repr();
+    match training {
+        Training::Modern { ivs, evs, nature } if modern
+            => calculate(base, ivs, evs, level, *nature),
+        Training::Retro { dvs, stat_exp } if !modern
+            => calculate_retro(generation, base, dvs, stat_exp, level),
+        _ => Err(StatError::Generation(generation)),
+    }
+}
+
+fn check_level(level: u8) -> Result<(), StatError> {
+    if level == 0 || level > MAX_LEVEL {
+        return Err(StatError::Level(level));
+    }
+    Ok(())
+}
@@ -0,0 +1,178 @@
+//! Type coverage and weaknesses of a team of Pokémon.
+//!
+//! A `Team` combines the types and abilities of its members into defensive
+//! profiles, and the types of their damaging moves into offensive coverage of
+//! every defending type combination. The analysis is of a generation: types
+//! and type combinations that did not exist yet are left out, and members'
+//! types and moves' types are those of the generation (see `Pokemon::types_in`
+//! and `Move::type_in`). For retro formats, also pass the efficacy table of
+//! the generation (see `EfficacyTable::for_generation`).
+
+use crate::{Ability, CombinedEfficacy, DefensiveProfile, Efficacy};
+use crate::EfficacyTable;
+use crate::Enum;
+use crate::moves::{DamageClass, Move};
+use crate::pokemon::{OneOrTwo, Pokemon};
+use crate::Type;
+use crate::versions::Generation;
+
+/// A Pokémon on a team.
+#[derive(Clone, Debug)]
+pub struct Member<'a> {
+    pub pokemon: &'a Pokemon,
+    /// The Pokémon's ability, which may change its matchups (see
+    /// `DefensiveProfile::with_ability`).
+    pub ability: Option<Ability>,
+    pub moves: Vec<&'a Move>,
+}
+
+impl<'a> Member<'a> {
+    /// The efficacy of each damage type against the member's types and
+    /// ability in the generation.
+    pub fn defensive_profile(
+        &self, efficacy: &EfficacyTable, generation: Generation
+    ) -> DefensiveProfile {
+        let types = self.pokemon.types_in(generation);
+        let profile = efficacy.defensive_profile(types);
+        match self.ability {
+            Some(ability) => profile.with_ability(ability, generation),
+            None => profile,
+        }
+    }
+
+    /// Iterate over the types of the member's damaging moves in the
+    /// generation.
+    pub fn attacking_types(
+        &self, generation: Generation
+    ) -> impl Iterator<Item = Type> + '_ {
+        self.moves.iter()
+            .filter(|m| m.damage_class != DamageClass::NonDamaging)
+            .map(move |m| m.type_in(generation))
+    }
+}
+
+/// The defending type combinations hit super effectively by a team.
+#[derive(Clone, Debug, Default)]
+pub struct Coverage {
+    /// The combinations hit super effectively by at least one damaging move.
+    pub covered: Vec<OneOrTwo<Type>>,
+    /// The combinations not hit super effectively by any damaging move.
+    pub uncovered: Vec<OneOrTwo<Type>>,
+}
+
+/// A team of Pokémon.
+#[derive(Clone, Debug, Default)]
+pub struct Team<'a> {
+    pub members: Vec<Member<'a>>,
+}
+
+impl<'a> Team<'a> {
+    /// The defensive profile of each member, in order.
+    pub fn defensive_profiles(
+        &self, efficacy: &EfficacyTable, generation: Generation
+    ) -> Vec<DefensiveProfile> {
+        self.members.iter()
+            .map(|m| m.defensive_profile(efficacy, generation))
+            .collect()
+    }
+
+    /// The offensive coverage of all the members' damaging moves.
+    pub fn coverage(
+        &self, efficacy: &EfficacyTable, generation: Generation
+    ) -> Coverage {
+        let mut attacking = [false; Type::COUNT];
+        let types = self.members.iter()
+            .flat_map(|m| m.attacking_types(generation));
+        for typ in types {
+            attacking[typ.repr() as usize] = true;
+        }
+        let mut coverage = Coverage::default();
+        for target in type_combinations(generation) {
+            let covered = types_in(generation)
+                .filter(|t| attacking[t.repr() as usize])
+                .any(|t| is_super(efficacy.combined(t, target)));
+            if covered {
+                coverage.covered.push(target);
+            } else {
+                coverage.uncovered.push(target);
+            }
+        }
+        coverage
+    }
+
+    /// The attacking types that more than one member is weak to, and that
+    /// more members are weak to than resist or are immune to.
+    pub fn shared_weaknesses(
+        &self, efficacy: &EfficacyTable, generation: Generation
+    ) -> Vec<Type> {
+        let profiles = self.defensive_profiles(efficacy, generation);
+        types_in(generation).filter(|&t| {
+            let weak = profiles.iter().filter(|p| is_super(p[t])).count();
+            let resist = profiles.iter()
+                .filter(|p| p[t].repr() < CombinedEfficacy::Regular.repr())
+                .count();
+            weak > 1 && weak > resist
+        }).collect()
+    }
+
+    /// The attacking types that at least one member is immune to.
+    pub fn immunities(
+        &self, efficacy: &EfficacyTable, generation: Generation
+    ) -> Vec<Type> {
+        let profiles = self.defensive_profiles(efficacy, generation);
+        types_in(generation)
+            .filter(|&t| profiles.iter().any(|p| p[t] == CombinedEfficacy::Not))
+            .collect()
+    }
+
+    /// Types that would patch holes in the team, best first.
+    ///
+    /// Each type scores a point for each uncovered type combination that it
+    /// hits super effectively as a move, and for each shared weakness that it
+    /// resists or is immune to as a Pokémon's type. Types that score no
+    /// points are not suggested.
+    pub fn suggested_types(
+        &self, efficacy: &EfficacyTable, generation: Generation
+    ) -> Vec<Type> {
+        let uncovered = self.coverage(efficacy, generation).uncovered;
+        let weaknesses = self.shared_weaknesses(efficacy, generation);
+        let mut scores: Vec<(Type, usize)> = types_in(generation).map(|t| {
+            let offense = uncovered.iter()
+                .filter(|&&target| is_super(efficacy.combined(t, target)))
+                .count();
+            let defense = weaknesses.iter()
+                .filter(|&&w| {
+                    efficacy[(w, t)].repr() < Efficacy::Regular.repr()
+                })
+                .count();
+            (t, offense + defense)
+        }).filter(|&(_, score)| score > 0).collect();
+        scores.sort_by_key(|&(t, score)| (std::cmp::Reverse(score), t.repr()));
+        scores.into_iter().map(|(t, _)| t).collect()
+    }
+}
+
+/// Iterate over every defending type combination in the generation: each
+/// single type, then each pair of different types.
+pub fn type_combinations(
+    generation: Generation
+) -> impl Iterator<Item = OneOrTwo<Type>> {
+    let singles = types_in(generation).map(OneOrTwo::One);
+    let pairs = types_in(generation).flat_map(move |t| {
+        types_in(generation)
+            .filter(move |u| u.repr() > t.repr())
+            .map(move |u| OneOrTwo::Two(t, u))
+    });
+    singles.chain(pairs)
+}
+
+/// Iterate over the types that exist in the generation.
+fn types_in(generation: Generation) -> impl Iterator<Item = Type> {
+    Type::VALUES.iter().cloned()
+        .filter(move |t| t.generation().repr() <= generation.repr())
+}
+
+/// Whether the efficacy is more than regular.
+fn is_super(efficacy: CombinedEfficacy) -> bool {
+    efficacy.repr() > CombinedEfficacy::Regular.repr()
+}
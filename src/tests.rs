@@ -1,15 +1,30 @@
 use crate::Ability;
+use crate::ByName;
 use crate::Efficacy;
 use crate::Nature;
 use crate::Type;
 use crate::Stat;
+use crate::capture;
+use crate::damage;
+use crate::evolution;
+use crate::experience;
 use crate::items;
 use crate::moves;
+use crate::overlay;
 use crate::pokemon;
+use crate::search;
+use crate::stats;
+use crate::team;
 use crate::versions;
+use crate::weight;
+use crate::DataSource;
+use crate::EfficacyTable;
 
 use crate::Enum;
+use crate::LazyPokedex;
 use crate::pokedex;
+use crate::Pokedex;
+use crate::PokedexHandle;
 use crate::to_pascal_case;
 
 #[test]
@@ -63,3 +78,834 @@ fn check_pascal_case() {
 fn load_pokedex() {
     pokedex();
 }
+
+#[test]
+fn load_error_names_file() {
+    let data = "damage_type_id,target_type_id,damage_factor\n1,1,42\n";
+    let mut files = std::collections::HashMap::new();
+    files.insert("type_efficacy.csv".to_string(), data.as_bytes().to_vec());
+    let source = DataSource::Memory(files);
+    let error = EfficacyTable::from_source(&source).err().unwrap();
+    assert_eq!(error.file, "type_efficacy.csv");
+    assert_eq!(error.error.line(), Some(2));
+}
+
+#[test]
+fn load_from_directory() {
+    let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("veekun").join("data");
+    let dex = Pokedex::from_source(&DataSource::Directory(dir)).unwrap();
+    assert_eq!(dex.moves[moves::MoveId(32)].power, 50);
+    let missing = DataSource::Memory(Default::default());
+    assert_eq!(moves::MoveTable::from_source(&missing).err().unwrap().file,
+               "moves.csv");
+}
+
+#[test]
+fn apply_overlay() {
+    use crate::overlay::Patch;
+    use crate::vcsv::FromCsv;
+    let data = "table,id,field,value\n\
+                move,33,power,50\n\
+                efficacy,10,9,100\n\
+                stat,25,6,110\n\
+                item,1,cost,1\n";
+    let overlay = overlay::Overlay::from_csv_data(data).unwrap();
+    let mut dex = Pokedex::new();
+    let audit = overlay.apply(&mut dex).unwrap();
+    assert_eq!(audit.len(), 4);
+    assert_eq!(dex.moves[moves::MoveId(32)].power, 50);
+    assert_eq!(dex.efficacy[(Type::Fire, Type::Steel)], Efficacy::Regular);
+    assert_eq!(audit[1].previous,
+               Patch::Efficacy(Type::Fire, Type::Steel, Efficacy::Super));
+    assert_eq!(audit[2].previous,
+               Patch::BaseStat(pokemon::PokemonId(24), Stat::Speed, 90));
+    let bad = overlay::Overlay(vec![
+        Patch::MovePower(moves::MoveId(0), 1),
+        Patch::ItemCost(items::ItemId(9999), 1),
+    ]);
+    assert_eq!(bad.apply(&mut dex).err().unwrap().index, 1);
+    assert_eq!(dex.moves[moves::MoveId(0)].power, 40);
+}
+
+#[test]
+fn swap_handle() {
+    let handle = PokedexHandle::default();
+    let shared = handle.clone();
+    let before = handle.get();
+    let mut dex = Pokedex::new();
+    dex.moves[moves::MoveId(32)].power = 35;
+    let previous = shared.replace(dex);
+    assert!(std::sync::Arc::ptr_eq(&before, &previous));
+    assert_eq!(before.moves[moves::MoveId(32)].power, 50);
+    assert_eq!(handle.get().moves[moves::MoveId(32)].power, 35);
+    assert_eq!(pokedex().moves[moves::MoveId(32)].power, 50);
+}
+
+#[test]
+fn check_kebab_case() {
+    use veekun::to_kebab_case;
+    assert_eq!(to_kebab_case("MasterBall"), "master-ball");
+    assert_eq!(to_kebab_case("HP"), "hp");
+    assert_eq!(to_kebab_case("OneHitKO"), "one-hit-ko");
+    assert_eq!(to_kebab_case("XDShadow"), "xd-shadow");
+    assert_eq!(to_kebab_case("Water1"), "water1");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip() {
+    let dex = pokedex();
+    let json = serde_json::to_string(&Stat::SpecialAttack).unwrap();
+    assert_eq!(json, "\"special-attack\"");
+    assert_eq!(serde_json::to_string(&moves::MoveId(32)).unwrap(), "33");
+    assert_eq!(serde_json::to_string(&moves::MoveId(0)).unwrap(), "1");
+    let bulbasaur = pokemon::SpeciesId(0);
+    assert_eq!(serde_json::to_string(&bulbasaur).unwrap(), "1");
+    let pound: moves::MoveId = serde_json::from_str("1").unwrap();
+    assert_eq!(pound, moves::MoveId(0));
+    let tackle = &dex.moves[moves::MoveId(32)];
+    let json = serde_json::to_value(tackle).unwrap();
+    assert_eq!(json["typ"], "normal");
+    assert_eq!(json["meta"]["flags"][0], "contact");
+    let back: moves::Move = serde_json::from_value(json).unwrap();
+    assert_eq!(back.id, tackle.id);
+    assert_eq!(back.meta.flags, tackle.meta.flags);
+    let json = serde_json::to_string(&dex.species).unwrap();
+    let species: pokemon::SpeciesTable = serde_json::from_str(&json).unwrap();
+    let bulbasaur = &species[pokemon::SpeciesId(0)];
+    assert_eq!(bulbasaur.pokemon[0].types.second(), Some(Type::Poison));
+}
+
+#[test]
+fn snapshot_matches_csv() {
+    let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("veekun").join("data");
+    let csv = pokemon::SpeciesTable::from_source(&DataSource::Directory(dir));
+    let csv = csv.unwrap();
+    let snapshot = &pokedex().species;
+    for i in 0..pokemon::SPECIES_COUNT {
+        let id = pokemon::SpeciesId(i as u16);
+        for (a, b) in csv[id].pokemon.iter().zip(&snapshot[id].pokemon) {
+            assert_eq!(a.moves, b.moves);
+        }
+    }
+}
+
+#[test]
+fn lazy_pokedex() {
+    let dex = LazyPokedex::new();
+    assert_eq!(dex.efficacy()[(Type::Fire, Type::Grass)], Efficacy::Super);
+    let bulbasaur = &dex.species()[pokemon::SpeciesId(0)].pokemon[0];
+    assert!(bulbasaur.moves.is_empty());
+    let learnset = &dex.learnsets()[bulbasaur.id];
+    let eager = &pokedex().species[pokemon::SpeciesId(0)].pokemon[0];
+    assert_eq!(learnset, &eager.moves);
+    let missing = DataSource::Memory(Default::default());
+    let broken = LazyPokedex::from_source(missing);
+    assert_eq!(broken.try_moves().err().unwrap().file, "moves.csv");
+    assert!(broken.preload_all().is_err());
+}
+
+#[test]
+fn lookup_by_name() {
+    assert_eq!(Stat::from_name("special-attack"), Ok(Stat::SpecialAttack));
+    assert_eq!(Stat::from_name("Special Attack"), Ok(Stat::SpecialAttack));
+    assert_eq!(Stat::from_name("HP"), Ok(Stat::HP));
+    assert_eq!(Ability::from_name("compound eyes"), Ok(Ability::Compoundeyes));
+    let level_up = moves::LearnMethod::from_name("level-up");
+    assert_eq!(level_up, Ok(moves::LearnMethod::LevelUp));
+    assert!(Type::from_name("Typeless").is_err());
+    assert_eq!("fire".parse::<ByName<Type>>(), Ok(ByName(Type::Fire)));
+    assert!("Sp. Atk".parse::<ByName<Stat>>().is_err());
+    let stat: ByName<Stat> = "special-defense".parse().unwrap();
+    assert_eq!(stat.0, Stat::SpecialDefense);
+    let dex = pokedex();
+    assert_eq!(dex.moves.by_name("thunder-punch").unwrap().id.0, 8);
+    assert_eq!(dex.moves.by_name("ThunderPunch").unwrap().id.0, 8);
+    assert_eq!(dex.items.by_name("Master Ball").unwrap().id.0, 1);
+    assert_eq!(dex.species.by_name("mr-mime").unwrap().id.0, 121);
+    assert_eq!(dex.species.by_name("Farfetch'd").unwrap().id.0, 82);
+    assert!(dex.species.by_name("missingno").is_none());
+    let mut species = pokemon::SpeciesTable::new();
+    species[pokemon::SpeciesId(0)].name = "Missingno".to_string();
+    species.reindex();
+    assert!(species.by_name("bulbasaur").is_none());
+    assert_eq!(species.by_name("missingno").unwrap().id.0, 0);
+    let mut items = items::ItemTable::new();
+    for id in 2..40 {
+        let item = items.0.get_mut(&items::ItemId(id)).unwrap();
+        item.name = "MasterBall".to_string();
+    }
+    items.reindex();
+    assert_eq!(items.by_name("master ball").unwrap().id.0, 1);
+}
+
+#[test]
+fn fuzzy_search() {
+    use search::{search, Entity};
+    let dex = pokedex();
+    let best = &search(dex, "thunderbot", 5)[0];
+    assert_eq!(best.entity, Entity::Move(dex.moves.by_name("thunderbolt")
+        .unwrap().id));
+    assert_eq!(best.distance, 1);
+    let best = &search(dex, "garchomp-mega", 5)[0];
+    assert_eq!(best.name, "Garchomp");
+    assert!(best.prefix);
+    let best = &search(dex, "levitate", 5)[0];
+    assert_eq!(best.entity, Entity::Ability(Ability::Levitate));
+    assert_eq!(best.distance, 0);
+    assert!(search(dex, "zzzzzzzzzzzz", 5).is_empty());
+}
+
+#[test]
+fn iterate_tables() {
+    let dex = pokedex();
+    assert_eq!(dex.moves.len(), moves::MOVE_COUNT);
+    assert_eq!((&dex.moves).into_iter().count(), moves::MOVE_COUNT);
+    assert!(dex.moves.get(moves::MoveId(moves::MOVE_COUNT as u16)).is_none());
+    assert_eq!(dex.items.iter().count(), dex.items.len());
+    assert_eq!(dex.items.get(items::ItemId(1)).unwrap().name, "MasterBall");
+    assert!(dex.items.get(items::ItemId(0)).is_none());
+    assert_eq!(dex.species.len(), pokemon::SPECIES_COUNT);
+    assert!(dex.species.get(pokemon::SpeciesId(9999)).is_none());
+    for (i, species) in dex.species.iter().enumerate() {
+        assert_eq!(species.id.0 as usize, i);
+    }
+    let pikachu = dex.species.find_pokemon(pokemon::PokemonId(24)).unwrap();
+    assert_eq!(pikachu.stats[Stat::Speed], 90);
+    assert!(dex.species.pokemon().count() > pokemon::SPECIES_COUNT);
+    let learnsets = pokemon::PokemonMoveTable::new();
+    assert_eq!(learnsets.iter().count(), learnsets.len());
+    assert_eq!((&learnsets).into_iter().last().unwrap().0.0 as usize + 1,
+        learnsets.len());
+    let efficacies = dex.efficacy.iter().collect::<Vec<_>>();
+    assert_eq!(efficacies.len(), dex.efficacy.len());
+    assert_eq!((&dex.efficacy).into_iter().count(), Type::COUNT * Type::COUNT);
+    let fire_grass = dex.efficacy.get((Type::Fire, Type::Grass));
+    assert_eq!(fire_grass, Some(Efficacy::Super));
+    assert!(efficacies.contains(&((Type::Fire, Type::Grass), Efficacy::Super)));
+    let palace = &dex.palace;
+    assert_eq!(palace.iter().count(), palace.len());
+    let hardy = palace.iter().filter(|&((n, _), _)| n == Nature::Hardy);
+    let (low, high) = hardy.fold((0, 0), |(l, h), (_, (low, high))| {
+        (l + low, h + high)
+    });
+    assert_eq!((low, high), (100, 100));
+}
+
+#[test]
+fn calculate_stats() {
+    use stats::Spread;
+    // Bulbapedia's example: a level 78 Adamant Garchomp.
+    let garchomp = pokedex().species.find_pokemon(pokemon::PokemonId(444));
+    let base = &garchomp.unwrap().stats;
+    let mut ivs = Spread::default();
+    let mut evs = Spread::default();
+    let values = [
+        (Stat::HP, 24, 74, 289),
+        (Stat::Attack, 12, 190, 278),
+        (Stat::Defense, 30, 91, 193),
+        (Stat::SpecialAttack, 16, 48, 135),
+        (Stat::SpecialDefense, 23, 84, 171),
+        (Stat::Speed, 5, 23, 171),
+    ];
+    for &(stat, iv, ev, _) in &values {
+        ivs[stat] = iv;
+        evs[stat] = ev;
+    }
+    let actual = stats::calculate(base, &ivs, &evs, 78, Nature::Adamant);
+    let actual = actual.unwrap();
+    for &(stat, iv, ev, expected) in &values {
+        assert_eq!(actual[stat], expected);
+        let range = stats::iv_range(
+            stat, base[stat], expected, ev, 78, Nature::Adamant).unwrap();
+        assert!(range.contains(&iv));
+    }
+    let ranges = stats::iv_ranges(base, &actual, &evs, 78, Nature::Adamant);
+    assert!(ranges.unwrap()[Stat::Speed].as_ref().unwrap().contains(&5));
+    evs[Stat::Speed] = 255;
+    let error = stats::calculate(base, &ivs, &evs, 78, Nature::Adamant);
+    assert_eq!(error, Err(stats::StatError::TotalEVs(742)));
+    ivs[Stat::HP] = 32;
+    let error = stats::calculate(base, &ivs, &evs, 0, Nature::Adamant);
+    assert_eq!(error, Err(stats::StatError::Level(0)));
+    let shedinja = stats::calculate_stat(Stat::HP, 1, 31, 252, 100,
+                                         Nature::Hardy);
+    assert_eq!(shedinja, 1);
+}
+
+#[test]
+fn calculate_retro_stats() {
+    use stats::{DVs, StatExp, Training};
+    use versions::Generation;
+    let mewtwo = pokedex().species.find_pokemon(pokemon::PokemonId(149));
+    let base = &mewtwo.unwrap().stats;
+    let dvs = DVs { attack: 15, defense: 15, speed: 15, special: 15 };
+    assert_eq!(dvs.hp(), 15);
+    let stat_exp = StatExp {
+        hp: 65535, attack: 65535, defense: 65535, speed: 65535,
+        special: 65535,
+    };
+    let training = Training::Retro { dvs, stat_exp };
+    let red = stats::calculate_for(Generation::I, base, &training, 100);
+    let red = red.unwrap();
+    assert_eq!(red[Stat::HP], 415);
+    assert_eq!(red[Stat::SpecialAttack], 406);
+    assert_eq!(red[Stat::SpecialDefense], 406);
+    let gold = stats::calculate_for(Generation::II, base, &training, 100);
+    assert_eq!(gold.unwrap()[Stat::SpecialDefense], 278);
+    let error = stats::calculate_for(Generation::III, base, &training, 100);
+    assert_eq!(error, Err(stats::StatError::Generation(Generation::III)));
+    let dvs = DVs { attack: 16, .. dvs };
+    let error = stats::calculate_retro(
+        Generation::I, base, &dvs, &Default::default(), 100);
+    assert_eq!(error, Err(stats::StatError::DV(Stat::Attack, 16)));
+}
+
+#[test]
+fn train_evs() {
+    use stats::{EvTrainer, MACHO_BRACE};
+    let dex = pokedex();
+    let find = |id| dex.species.find_pokemon(pokemon::PokemonId(id)).unwrap();
+    // Bidoof yields 1 HP EV, Machop 1 Attack, Blissey 3 HP.
+    assert_eq!(find(398).ev_yield[Stat::HP], 1);
+    assert_eq!(find(65).ev_yield[Stat::Attack], 1);
+    assert_eq!(find(241).ev_yield[Stat::HP], 3);
+    let mut trainer = EvTrainer {
+        held_item: Some(MACHO_BRACE),
+        pokerus: true,
+        .. Default::default()
+    };
+    let gained = trainer.defeat(find(65));
+    assert_eq!(gained[Stat::Attack], 4);
+    trainer.held_item = Some(items::ItemId(271));
+    trainer.pokerus = false;
+    assert_eq!(trainer.defeat(find(65))[Stat::HP], 4);
+    trainer.defeat_all(std::iter::repeat_n(find(241), 100));
+    assert_eq!(trainer.evs[Stat::HP], 255);
+    trainer.held_item = Some(items::ItemId(266));
+    trainer.defeat_all(std::iter::repeat_n(find(65), 100));
+    assert_eq!(trainer.evs[Stat::Attack], 255);
+    assert_eq!(trainer.evs.total(), 510);
+    trainer.held_item = None;
+    assert_eq!(trainer.defeat(find(16)).total(), 0);
+    // EVs set beyond the total limit by hand gain nothing, without panicking.
+    trainer.evs = stats::Spread([200; pokemon::PERMANENT_STATS]);
+    assert!(trainer.evs.total() > stats::MAX_TOTAL_EVS);
+    assert_eq!(trainer.defeat(find(241)).total(), 0);
+    assert_eq!(trainer.evs[Stat::HP], 200);
+}
+
+#[test]
+fn experience_curves() {
+    use experience::{Knockout, Trade};
+    use pokemon::GrowthRate;
+    use versions::Generation;
+    let totals = [
+        (GrowthRate::Slow, 1_250_000),
+        (GrowthRate::Medium, 1_000_000),
+        (GrowthRate::Fast, 800_000),
+        (GrowthRate::MediumSlow, 1_059_860),
+        (GrowthRate::Erratic, 600_000),
+        (GrowthRate::Fluctuating, 1_640_000),
+    ];
+    for &(rate, total) in &totals {
+        assert_eq!(rate.experience(1), 0);
+        assert_eq!(rate.experience(100), total);
+        assert_eq!(rate.level(total), 100);
+        for level in 2..100 {
+            assert!(rate.experience(level) < rate.experience(level + 1));
+            assert_eq!(rate.level(rate.experience(level)), level);
+        }
+    }
+    let dex = pokedex();
+    let bulbasaur = &dex.species[pokemon::SpeciesId(0)];
+    assert_eq!(bulbasaur.growth_rate, GrowthRate::MediumSlow);
+    assert_eq!(bulbasaur.growth_rate.experience(5), 135);
+    assert_eq!(bulbasaur.pokemon[0].base_experience, 64);
+    let mut knockout = Knockout {
+        base_experience: 64,
+        fainted_level: 5,
+        winner_level: 5,
+        .. Default::default()
+    };
+    assert_eq!(knockout.experience(Generation::IV), 45);
+    assert_eq!(knockout.experience(Generation::V), 65);
+    knockout.trainer_owned = true;
+    knockout.trade = Trade::International;
+    assert_eq!(knockout.experience(Generation::III), 100);
+    assert_eq!(knockout.experience(Generation::IV), 113);
+    knockout.winner_level = 50;
+    assert!(knockout.experience(Generation::V) < 45);
+}
+
+#[test]
+fn capture_probability() {
+    use capture::{balls, Capture, CaptureError};
+    use moves::Ailment;
+    use versions::Generation;
+    let dex = pokedex();
+    let bulbasaur = &dex.species[pokemon::SpeciesId(0)];
+    assert_eq!(bulbasaur.capture_rate, 45);
+    let mut capture = Capture::new(
+        bulbasaur, &bulbasaur.pokemon[0], 5, 20, 20, balls::POKE_BALL);
+    let full_hp = capture.probability().unwrap();
+    capture.hp = 1;
+    let low_hp = capture.probability().unwrap();
+    capture.ailment = Ailment::Sleep;
+    let asleep = capture.probability().unwrap();
+    assert!(full_hp < low_hp && low_hp < asleep && asleep < 1.0);
+    capture.generation = Generation::IV;
+    capture.ailment = Ailment::None;
+    capture.hp = 20;
+    assert!((capture.probability().unwrap() - 0.0625).abs() < 0.001);
+    for &generation in Generation::VALUES {
+        capture.generation = generation;
+        let p = capture.probability().unwrap();
+        assert!(p > 0.0 && p < 1.0);
+        let caught = (0..2000).filter(|&seed| {
+            capture.simulate_seeded(seed).unwrap().caught
+        }).count() as f64 / 2000.0;
+        assert!((caught - p).abs() < 0.05, "{:?}", generation);
+    }
+    assert_eq!(capture.simulate_seeded(7), capture.simulate_seeded(7));
+    capture.ball = balls::MASTER_BALL;
+    assert_eq!(capture.probability(), Ok(1.0));
+    capture.ball = balls::DUSK_BALL;
+    capture.generation = Generation::III;
+    let error = Err(CaptureError::Ball(balls::DUSK_BALL, Generation::III));
+    assert_eq!(capture.probability(), error);
+    capture.hp = 0;
+    assert_eq!(capture.probability(), Err(CaptureError::HP(0, 20)));
+}
+
+#[test]
+fn species_metadata() {
+    use pokemon::{Color, Habitat, Shape};
+    use versions::Generation;
+    let dex = pokedex();
+    let pikachu = dex.species.by_name("pikachu").unwrap();
+    assert_eq!(pikachu.color, Color::Yellow);
+    assert_eq!(pikachu.shape, Shape::Quadruped);
+    assert_eq!(pikachu.habitat, Some(Habitat::Forest));
+    assert_eq!(pikachu.base_happiness, 70);
+    assert_eq!(pikachu.hatch_counter, 10);
+    assert_eq!(pikachu.hatch_steps(Generation::V), Some(2805));
+    assert_eq!(pikachu.hatch_steps(Generation::III), Some(2816));
+    assert_eq!(pikachu.hatch_steps(Generation::I), None);
+    assert!(pikachu.has_gender_differences);
+    assert!(!pikachu.is_baby);
+    assert!(dex.species.by_name("pichu").unwrap().is_baby);
+    assert!(dex.species.by_name("deoxys").unwrap().forms_switchable);
+    assert_eq!(dex.species.by_name("garchomp").unwrap().habitat, None);
+    let raichu = dex.species.by_name("raichu").unwrap();
+    assert_eq!(raichu.order, pikachu.order + 1);
+}
+
+#[test]
+fn weight_based_power() {
+    use weight::WeightModifiers;
+    let dex = pokedex();
+    for &(id, name) in &[
+        (weight::LOW_KICK, "LowKick"), (weight::GRASS_KNOT, "GrassKnot"),
+        (weight::HEAVY_SLAM, "HeavySlam"), (weight::HEAT_CRASH, "HeatCrash"),
+    ] {
+        assert_eq!(dex.moves[id].name, name);
+    }
+    let pikachu = dex.species.find_pokemon(pokemon::PokemonId(24)).unwrap();
+    assert_eq!((pikachu.height, pikachu.weight), (4, 60));
+    let snorlax = dex.species.find_pokemon(pokemon::PokemonId(142)).unwrap();
+    assert_eq!(snorlax.weight, 4600);
+    assert_eq!(weight::low_kick_power(pikachu.weight), 20);
+    assert_eq!(weight::low_kick_power(snorlax.weight), 120);
+    let power = weight::move_power(
+        weight::HEAVY_SLAM, snorlax.weight, pikachu.weight);
+    assert_eq!(power, Some(120));
+    assert_eq!(weight::heavy_slam_power(pikachu.weight, snorlax.weight), 40);
+    assert_eq!(weight::heavy_slam_power(300, 100), 80);
+    assert_eq!(weight::move_power(moves::MoveId(32), 1, 1), None);
+    let modifiers = WeightModifiers {
+        autotomize: 2,
+        held_item: Some(weight::FLOAT_STONE),
+        .. Default::default()
+    };
+    assert_eq!(weight::battle_weight(snorlax.weight, &modifiers), 1300);
+    assert_eq!(weight::battle_weight(pikachu.weight, &modifiers), 1);
+    let modifiers = WeightModifiers {
+        ability: Some(Ability::HeavyMetal),
+        .. Default::default()
+    };
+    assert_eq!(weight::battle_weight(snorlax.weight, &modifiers), 9200);
+}
+
+#[test]
+fn evolution_conditions() {
+    use pokemon::{EvolutionTrigger, SpeciesId, TimeOfDay};
+    let dex = pokedex();
+    let espeon = &dex.species.by_name("espeon").unwrap().evolves_from;
+    assert_eq!(espeon.len(), 1);
+    assert_eq!(espeon[0].from_id, SpeciesId(132));
+    assert_eq!(espeon[0].min_happiness, Some(220));
+    assert_eq!(espeon[0].time_of_day, Some(TimeOfDay::Day));
+    let milotic = &dex.species.by_name("milotic").unwrap().evolves_from;
+    assert_eq!(milotic.len(), 2);
+    assert_eq!(milotic[0].min_beauty, Some(171));
+    assert_eq!(milotic[1].trigger, EvolutionTrigger::Trade);
+    assert_eq!(milotic[1].held_item, Some(items::ItemId(580)));
+    assert!(milotic.iter().all(|e| e.from_id == SpeciesId(348)));
+    let magnezone = &dex.species.by_name("magnezone").unwrap().evolves_from;
+    let locations: Vec<_> = magnezone.iter().map(|e| e.location).collect();
+    assert_eq!(locations, [Some(10), Some(379)]);
+    let togekiss = &dex.species.by_name("togekiss").unwrap().evolves_from;
+    assert_eq!(togekiss[0].trigger_item, Some(items::ItemId(107)));
+    let mantine = &dex.species.by_name("mantine").unwrap().evolves_from;
+    assert_eq!(mantine[0].party_species, Some(SpeciesId(222)));
+    let escavalier = dex.species.by_name("escavalier").unwrap();
+    assert_eq!(escavalier.evolves_from[0].trade_species, Some(SpeciesId(615)));
+    assert_eq!(escavalier.pre_evolution(), Some(SpeciesId(587)));
+    assert!(dex.species.by_name("bulbasaur").unwrap().evolves_from.is_empty());
+}
+
+#[test]
+fn evolution_graph() {
+    use evolution::{Conditions, EvolutionGraph};
+    use pokemon::{EvolutionTrigger, TimeOfDay};
+    let dex = pokedex();
+    let graph = EvolutionGraph::new(&dex.species);
+    let id = |name| dex.species.by_name(name).unwrap().id;
+    assert_eq!(graph.evolves_into(id("bulbasaur")), [id("ivysaur")]);
+    assert_eq!(graph.root(id("venusaur")), id("bulbasaur"));
+    assert_eq!(graph.stage(id("venusaur")), 2);
+    assert_eq!(graph.chain(id("ivysaur")),
+               [id("bulbasaur"), id("ivysaur"), id("venusaur")]);
+    assert_eq!(graph.evolves_into(id("eevee")).len(), 7);
+    assert_eq!(graph.evolves_into(id("tyrogue")).len(), 3);
+    let wurmple = graph.stages(id("beautifly"));
+    assert_eq!(wurmple.len(), 3);
+    assert_eq!(wurmple[1], [id("silcoon"), id("cascoon")]);
+    assert!(graph.is_branching(id("dustox")));
+    assert!(!graph.is_branching(id("charmander")));
+    assert_eq!(graph.root(id("pikachu")), id("pichu"));
+    let mut conditions = Conditions {
+        trigger: EvolutionTrigger::LevelUp,
+        level: 30,
+        happiness: 250,
+        time_of_day: TimeOfDay::Night,
+        .. Default::default()
+    };
+    let possible = |c: &Conditions, name| evolution::possible_evolutions(
+        &dex.species, &graph, id(name), c);
+    assert_eq!(possible(&conditions, "eevee"), [id("umbreon")]);
+    conditions.time_of_day = TimeOfDay::Day;
+    assert_eq!(possible(&conditions, "eevee"), [id("espeon")]);
+    conditions.trigger = EvolutionTrigger::UseItem;
+    conditions.used_item = Some(items::ItemId(82));
+    assert_eq!(possible(&conditions, "eevee"), [id("flareon")]);
+    conditions.trigger = EvolutionTrigger::LevelUp;
+    conditions.level = 20;
+    conditions.attack = 50;
+    conditions.defense = 40;
+    assert_eq!(possible(&conditions, "tyrogue"), [id("hitmonlee")]);
+    conditions.level = 19;
+    assert!(possible(&conditions, "tyrogue").is_empty());
+}
+
+#[test]
+fn evolve_owned_pokemon() {
+    use evolution::{Environment, Event, EvolutionGraph, OwnedPokemon};
+    let dex = pokedex();
+    let graph = EvolutionGraph::new(&dex.species);
+    let id = |name| dex.species.by_name(name).unwrap().id;
+    let mut environment = Environment {
+        poke_ball: true,
+        .. Default::default()
+    };
+    let nincada = OwnedPokemon {
+        species: id("nincada"),
+        level: 20,
+        .. Default::default()
+    };
+    let evolve = |pokemon: &OwnedPokemon, event, env: &Environment|
+        evolution::evolve(&dex.species, &graph, pokemon, &event, env);
+    let evolution = evolve(&nincada, Event::LevelUp, &environment).unwrap();
+    assert_eq!(evolution.species, id("ninjask"));
+    assert_eq!(evolution.shed, Some(id("shedinja")));
+    environment.party = vec![id("pikachu"); 5];
+    let evolution = evolve(&nincada, Event::LevelUp, &environment).unwrap();
+    assert_eq!(evolution.shed, None);
+    let tyrogue = OwnedPokemon {
+        species: id("tyrogue"),
+        level: 20,
+        attack: 40,
+        defense: 40,
+        .. Default::default()
+    };
+    let evolution = evolve(&tyrogue, Event::LevelUp, &environment).unwrap();
+    assert_eq!(evolution.species, id("hitmontop"));
+    let mut onix = OwnedPokemon {
+        species: id("onix"),
+        level: 5,
+        .. Default::default()
+    };
+    assert!(evolve(&onix, Event::LevelUp, &environment).is_none());
+    assert!(evolve(&onix, Event::Trade(None), &environment).is_none());
+    onix.held_item = Some(items::ItemId(210));
+    let evolution = evolve(&onix, Event::Trade(None), &environment).unwrap();
+    assert_eq!(evolution.species, id("steelix"));
+    assert!(evolution.consumes_held_item);
+}
+
+#[test]
+fn damage_gen_v() {
+    use damage::{Battler, Circumstances, Weather};
+    use pokemon::OneOrTwo;
+    use stats::Spread;
+    let dex = pokedex();
+    let glaceon = Battler {
+        level: 75,
+        types: OneOrTwo::One(Type::Ice),
+        stats: Spread([201, 123, 211, 112, 192, 148]),
+        .. Default::default()
+    };
+    let garchomp = Battler {
+        level: 65,
+        types: OneOrTwo::Two(Type::Dragon, Type::Ground),
+        stats: Spread([212, 210, 163, 163, 142, 121]),
+        .. Default::default()
+    };
+    let ice_fang = dex.moves.by_name("ice-fang").unwrap();
+    let mut circumstances = Default::default();
+    let calculate = |m, user, target, c: &Circumstances| damage::damage(
+        &dex.efficacy, m, user, target, c).unwrap();
+    let result = calculate(ice_fang, &glaceon, &garchomp, &circumstances);
+    assert_eq!(result.rolls.len(), 16);
+    assert_eq!((result.min(), result.max()), (168, 196));
+    assert_eq!(result.ko_chance(168), 1.0);
+    assert_eq!(result.ko_chance(197), 0.0);
+    assert!(result.ko_chance(190) > 0.0 && result.ko_chance(190) < 0.5);
+    assert_eq!(result.ko_chance_in(2, 212), 1.0);
+    circumstances.critical = true;
+    let critical = calculate(ice_fang, &glaceon, &garchomp, &circumstances);
+    assert!(critical.min() > result.max());
+    circumstances = Circumstances {
+        weather: Weather::Rain,
+        .. Default::default()
+    };
+    let earthquake = dex.moves.by_name("earthquake").unwrap();
+    let mut flying = glaceon;
+    flying.types = OneOrTwo::Two(Type::Normal, Type::Flying);
+    let result = calculate(earthquake, &garchomp, &flying, &circumstances);
+    assert_eq!(result.max(), 0);
+    let swords_dance = dex.moves.by_name("swords-dance").unwrap();
+    assert!(damage::damage(&dex.efficacy, swords_dance, &garchomp, &glaceon,
+                           &circumstances).is_none());
+    assert_eq!(damage::apply_stage(100, 2), 200);
+    assert_eq!(damage::apply_stage(100, -2), 50);
+}
+
+#[test]
+fn damage_by_generation() {
+    use damage::{Battler, Circumstances};
+    use pokemon::OneOrTwo;
+    use stats::Spread;
+    use versions::Generation;
+    let dex = pokedex();
+    let calculate = |generation, m, user, target, c: &Circumstances|
+        damage::damage_for(generation, &dex.efficacy, m, user, target, c)
+            .unwrap();
+    let glaceon = Battler {
+        level: 75,
+        types: OneOrTwo::One(Type::Ice),
+        stats: Spread([201, 123, 211, 112, 192, 148]),
+        .. Default::default()
+    };
+    let garchomp = Battler {
+        level: 65,
+        types: OneOrTwo::Two(Type::Dragon, Type::Ground),
+        stats: Spread([212, 210, 163, 163, 142, 121]),
+        .. Default::default()
+    };
+    let ice_fang = dex.moves.by_name("ice-fang").unwrap();
+    let normal = Default::default();
+    let result = calculate(Generation::IV, ice_fang, &glaceon, &garchomp,
+                           &normal);
+    assert_eq!((result.min(), result.max()), (168, 196));
+    // Ice moves are special before Generation IV.
+    let result = calculate(Generation::III, ice_fang, &glaceon, &garchomp,
+                           &normal);
+    assert_eq!((result.min(), result.max()), (346, 408));
+    let tauros = Battler {
+        level: 100,
+        types: OneOrTwo::One(Type::Normal),
+        stats: Spread([353, 298, 288, 318, 238, 238]),
+        .. Default::default()
+    };
+    let chansey = Battler {
+        level: 100,
+        types: OneOrTwo::One(Type::Normal),
+        stats: Spread([703, 108, 108, 198, 308, 308]),
+        .. Default::default()
+    };
+    let body_slam = dex.moves.by_name("body-slam").unwrap();
+    let result = calculate(Generation::I, body_slam, &tauros, &chansey,
+                           &normal);
+    assert_eq!(result.rolls.len(), 39);
+    assert_eq!((result.min(), result.max()), (251, 295));
+    let critical = Circumstances {
+        critical: true,
+        .. Default::default()
+    };
+    let result = calculate(Generation::I, body_slam, &tauros, &chansey,
+                           &critical);
+    assert_eq!((result.min(), result.max()), (490, 576));
+    let earthquake = dex.moves.by_name("earthquake").unwrap();
+    let result = calculate(Generation::II, earthquake, &tauros, &chansey,
+                           &normal);
+    assert_eq!((result.min(), result.max()), (197, 232));
+    let mut soft_sand = tauros;
+    soft_sand.held_item = Some(items::ItemId(214));
+    let result = calculate(Generation::II, earthquake, &soft_sand, &chansey,
+                           &normal);
+    assert_eq!((result.min(), result.max()), (217, 255));
+    assert_eq!(damage::type_damage_class(Type::Steel),
+               moves::DamageClass::Physical);
+    assert_eq!(damage::type_damage_class(Type::Dark),
+               moves::DamageClass::Special);
+    assert_eq!(damage::critical_chance_by_speed(110, false), 55.0 / 256.0);
+    assert_eq!(damage::critical_chance_by_speed(110, true), 255.0 / 256.0);
+}
+
+#[test]
+fn combined_efficacy() {
+    use crate::CombinedEfficacy;
+    use pokemon::OneOrTwo;
+    let dex = pokedex();
+    let garchomp = OneOrTwo::Two(Type::Dragon, Type::Ground);
+    assert_eq!(dex.efficacy.against(Type::Ice, garchomp), 4.0);
+    assert_eq!(dex.efficacy.against(Type::Electric, garchomp), 0.0);
+    assert_eq!(dex.efficacy.against(Type::Fire, garchomp), 0.5);
+    let scizor = OneOrTwo::Two(Type::Bug, Type::Steel);
+    assert_eq!(dex.efficacy.combined(Type::Grass, scizor),
+               CombinedEfficacy::DoubleNotVery);
+    assert_eq!(dex.efficacy.against(Type::Water, OneOrTwo::One(Type::Fire)),
+               2.0);
+    let gyarados = dex.species.by_name("gyarados").unwrap().pokemon[0]
+        .defensive_profile(&dex.efficacy);
+    assert_eq!(gyarados[Type::Electric], CombinedEfficacy::DoubleSuper);
+    assert_eq!(gyarados.weaknesses().collect::<Vec<_>>(),
+               [Type::Rock, Type::Electric]);
+    assert_eq!(gyarados.immunities().collect::<Vec<_>>(), [Type::Ground]);
+    assert_eq!(gyarados.resistances().count(), 5);
+    assert_eq!(CombinedEfficacy::from(Efficacy::NotVery),
+               CombinedEfficacy::NotVery);
+}
+
+#[test]
+fn efficacy_by_generation() {
+    use versions::Generation;
+    let dex = pokedex();
+    let gen_i = EfficacyTable::for_generation(Generation::I);
+    assert_eq!(gen_i[(Type::Ghost, Type::Psychic)], Efficacy::Not);
+    assert_eq!(gen_i[(Type::Bug, Type::Poison)], Efficacy::Super);
+    assert_eq!(gen_i[(Type::Poison, Type::Bug)], Efficacy::Super);
+    assert_eq!(gen_i[(Type::Ice, Type::Fire)], Efficacy::Regular);
+    assert_eq!(gen_i[(Type::Fighting, Type::Steel)], Efficacy::Regular);
+    assert_eq!(gen_i[(Type::Dark, Type::Psychic)], Efficacy::Regular);
+    let gen_ii = EfficacyTable::for_generation(Generation::II);
+    assert_eq!(gen_ii[(Type::Ghost, Type::Psychic)], Efficacy::Super);
+    assert_eq!(gen_ii[(Type::Ice, Type::Fire)], Efficacy::NotVery);
+    assert_eq!(Type::Steel.generation(), Generation::II);
+    let magnemite = &dex.species.by_name("magnemite").unwrap().pokemon[0];
+    assert!(magnemite.types_in(Generation::I).second().is_none());
+    assert_eq!(magnemite.types_in(Generation::II).second(), Some(Type::Steel));
+    let bite = dex.moves.by_name("bite").unwrap();
+    assert_eq!(bite.type_in(Generation::I), Type::Normal);
+    assert_eq!(bite.type_in(Generation::II), Type::Dark);
+}
+
+#[test]
+fn team_coverage() {
+    use crate::CombinedEfficacy;
+    use pokemon::OneOrTwo;
+    use team::{Member, Team};
+    use versions::Generation;
+    let dex = pokedex();
+    let pokemon = |name| &dex.species.by_name(name).unwrap().pokemon[0];
+    let moves = |names: &[&str]| names.iter()
+        .map(|&name| dex.moves.by_name(name).unwrap())
+        .collect::<Vec<_>>();
+    let team = Team {
+        members: vec![
+            Member {
+                pokemon: pokemon("charizard"),
+                ability: Some(Ability::Blaze),
+                moves: moves(&["flamethrower"]),
+            },
+            Member {
+                pokemon: pokemon("gyarados"),
+                ability: Some(Ability::Intimidate),
+                moves: moves(&["waterfall", "dragon-dance"]),
+            },
+            Member {
+                pokemon: pokemon("gengar"),
+                ability: Some(Ability::Levitate),
+                moves: moves(&["shadow-ball"]),
+            },
+        ],
+    };
+    let v = Generation::V;
+    assert_eq!(team.members[1].attacking_types(v).collect::<Vec<_>>(),
+               [Type::Water]);
+    let gengar = &team.members[2];
+    assert_eq!(gengar.pokemon.defensive_profile(&dex.efficacy)[Type::Ground],
+               CombinedEfficacy::Super);
+    assert_eq!(gengar.defensive_profile(&dex.efficacy, v)[Type::Ground],
+               CombinedEfficacy::Not);
+    let profile = dex.efficacy.defensive_profile(OneOrTwo::One(Type::Grass));
+    assert_eq!(profile.with_ability(Ability::FlashFire, v)[Type::Fire],
+               CombinedEfficacy::Not);
+    assert_eq!(profile.with_ability(Ability::ThickFat, v)[Type::Fire],
+               CombinedEfficacy::Regular);
+    let coverage = team.coverage(&dex.efficacy, v);
+    assert_eq!(coverage.covered.len() + coverage.uncovered.len(), 153);
+    assert!(coverage.covered.iter().any(|t| t.first() == Type::Grass
+                                        && t.second().is_none()));
+    assert!(coverage.uncovered.iter().any(|t| t.first() == Type::Normal
+                                          && t.second().is_none()));
+    assert!(team.shared_weaknesses(&dex.efficacy, v).contains(&Type::Electric));
+    assert!(!team.shared_weaknesses(&dex.efficacy, v).contains(&Type::Ground));
+    let immunities = team.immunities(&dex.efficacy, v);
+    assert_eq!(immunities, [Type::Normal, Type::Fighting, Type::Ground]);
+    assert!(team.suggested_types(&dex.efficacy, v).contains(&Type::Ground));
+    // Generation I has no Dark or Steel types, and no abilities.
+    let gen1 = Generation::I;
+    let efficacy = EfficacyTable::for_generation(gen1);
+    assert_eq!(team::type_combinations(gen1).count(), 120);
+    let coverage = team.coverage(&efficacy, gen1);
+    assert!(coverage.covered.iter().chain(&coverage.uncovered)
+        .all(|t| t.first() != Type::Steel && t.second() != Some(Type::Steel)));
+    assert_eq!(gengar.defensive_profile(&efficacy, gen1)[Type::Ground],
+               CombinedEfficacy::Super);
+    let bite = Member {
+        moves: moves(&["bite"]),
+        .. team.members[2].clone()
+    };
+    assert_eq!(bite.attacking_types(gen1).collect::<Vec<_>>(), [Type::Normal]);
+    // Lightningrod only redirects Electric-type moves before Generation V.
+    let rod = Member {
+        ability: Some(Ability::Lightningrod),
+        .. team.members[1].clone()
+    };
+    let rod_team = Team { members: vec![rod.clone()] };
+    let iv = Generation::IV;
+    let efficacy = EfficacyTable::for_generation(iv);
+    assert!(rod.defensive_profile(&efficacy, iv).weaknesses()
+        .any(|t| t == Type::Electric));
+    assert!(!rod_team.immunities(&efficacy, iv).contains(&Type::Electric));
+    assert_eq!(rod.defensive_profile(&dex.efficacy, v)[Type::Electric],
+               CombinedEfficacy::Not);
+    assert!(rod_team.immunities(&dex.efficacy, v).contains(&Type::Electric));
+    let sipper = profile.with_ability(Ability::SapSipper, iv);
+    assert_eq!(sipper[Type::Grass], profile[Type::Grass]);
+}
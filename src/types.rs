@@ -1,7 +1,11 @@
+use crate::Ability;
 use crate::enums::*;
 use crate::FromVeekun;
+use crate::DataSource;
+use crate::LoadError;
+use crate::pokemon::OneOrTwo;
+use crate::versions::Generation;
 use crate::vcsv;
-use crate::vcsv::FromCsv;
 use crate::vdata;
 
 /// Level of efficacy of some type combination.
@@ -32,6 +36,60 @@ impl Default for Efficacy {
     fn default() -> Self { Efficacy::Regular }
 }
 
+/// Level of efficacy of a type against one or two types combined.
+///
+/// Unlike `Efficacy`, this includes the levels only possible against two
+/// types.
+#[EnumRepr(type = "i8")]
+pub enum CombinedEfficacy {
+    /// Attacks have no effect.
+    Not = -3,
+    /// Attacks do a quarter damage.
+    DoubleNotVery,
+    /// Attacks do half damage.
+    NotVery,
+    /// Attacks do regular damage.
+    Regular,
+    /// Attacks do double damage.
+    Super,
+    /// Attacks do quadruple damage.
+    DoubleSuper,
+}
+
+impl CombinedEfficacy {
+    /// Combine the efficacies against each of a Pokémon's types.
+    pub fn combine(efficacies: OneOrTwo<Efficacy>) -> Self {
+        let first = efficacies.first();
+        let second = efficacies.second().unwrap_or(Efficacy::Regular);
+        if first == Efficacy::Not || second == Efficacy::Not {
+            return CombinedEfficacy::Not;
+        }
+        Self::from_repr(first.repr() + second.repr())
+            .unwrap_or(CombinedEfficacy::Regular)
+    }
+
+    pub fn modifier(self) -> f64 {
+        match self {
+            CombinedEfficacy::Not => 0.0,
+            CombinedEfficacy::DoubleNotVery => 0.25,
+            CombinedEfficacy::NotVery => 0.5,
+            CombinedEfficacy::Regular => 1.0,
+            CombinedEfficacy::Super => 2.0,
+            CombinedEfficacy::DoubleSuper => 4.0,
+        }
+    }
+}
+
+impl Default for CombinedEfficacy {
+    fn default() -> Self { CombinedEfficacy::Regular }
+}
+
+impl From<Efficacy> for CombinedEfficacy {
+    fn from(efficacy: Efficacy) -> Self {
+        Self::combine(OneOrTwo::One(efficacy))
+    }
+}
+
 impl FromVeekun for Efficacy {
     type Intermediate = u8;
 
@@ -79,6 +137,16 @@ pub enum Type {
     Dark,
 }
 
+impl Type {
+    /// The generation the type was introduced.
+    pub fn generation(self) -> Generation {
+        match self {
+            Type::Dark | Type::Steel => Generation::II,
+            _ => Generation::I,
+        }
+    }
+}
+
 impl Default for Type {
     fn default() -> Self { Type::Normal }
 }
@@ -92,16 +160,149 @@ impl FromVeekun for Type {
 }
 
 /// Table of the efficacies of type combinations.
-#[derive(Default)]
+///
+/// The included data is the chart of Generations II to V; see
+/// `for_generation` for Generation I.
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EfficacyTable([[Efficacy; Type::COUNT]; Type::COUNT]);
 
 impl EfficacyTable {
     /// Creates a type efficacy table from the included Veekun CSV data.
     pub fn new() -> Self {
-        Self::from_csv_data(vdata::EFFICACY).unwrap()
+        Self::try_new().unwrap()
+    }
+
+    /// Like `new`, but returns an error instead of panicking.
+    pub fn try_new() -> Result<Self, LoadError> {
+        Self::from_source(&DataSource::Embedded)
+    }
+
+    /// Create the table from the CSV files in the given source.
+    pub fn from_source(source: &DataSource) -> Result<Self, LoadError> {
+        source.load(vdata::files::EFFICACY)
+    }
+
+    /// Creates the type efficacy table of the given generation from the
+    /// included Veekun CSV data.
+    pub fn for_generation(generation: Generation) -> Self {
+        Self::new().to_generation(generation)
+    }
+
+    /// Adapt a table of the chart of Generations II to V to the chart of the
+    /// given generation.
+    ///
+    /// In Generation I, Ghost has no effect on Psychic due to a bug, Bug and
+    /// Poison are super effective against each other, and Ice is regularly
+    /// effective against Fire. Dark and Steel do not exist, so have regular
+    /// efficacy in either direction.
+    pub fn to_generation(&self, generation: Generation) -> Self {
+        let mut table = self.clone();
+        if generation == Generation::I {
+            for &typ in Type::VALUES.iter() {
+                for &new in &[Type::Dark, Type::Steel] {
+                    table[(new, typ)] = Efficacy::Regular;
+                    table[(typ, new)] = Efficacy::Regular;
+                }
+            }
+            table[(Type::Ghost, Type::Psychic)] = Efficacy::Not;
+            table[(Type::Bug, Type::Poison)] = Efficacy::Super;
+            table[(Type::Poison, Type::Bug)] = Efficacy::Super;
+            table[(Type::Ice, Type::Fire)] = Efficacy::Regular;
+        }
+        table
+    }
+
+    /// The efficacy of the damage type against the target's types.
+    pub fn combined(
+        &self, damage: Type, target: OneOrTwo<Type>
+    ) -> CombinedEfficacy {
+        CombinedEfficacy::combine(match target {
+            OneOrTwo::One(t) => OneOrTwo::One(self[(damage, t)]),
+            OneOrTwo::Two(t, u)
+                => OneOrTwo::Two(self[(damage, t)], self[(damage, u)]),
+        })
+    }
+
+    /// The damage modifier of the damage type against the target's types,
+    /// from 0 to 4.
+    pub fn against(&self, damage: Type, target: OneOrTwo<Type>) -> f64 {
+        self.combined(damage, target).modifier()
+    }
+
+    /// The efficacy of each damage type against the target's types.
+    pub fn defensive_profile(
+        &self, target: OneOrTwo<Type>
+    ) -> DefensiveProfile {
+        let mut profile = DefensiveProfile::default();
+        for &damage in Type::VALUES.iter() {
+            profile.0[damage.repr() as usize] = self.combined(damage, target);
+        }
+        profile
+    }
+
+    /// Iterate over all (damage, target) type combinations and their
+    /// efficacies.
+    pub fn iter(&self) -> EfficacyIter<'_> {
+        EfficacyIter { table: self, next: 0 }
+    }
+
+    /// The number of (damage, target) type combinations.
+    pub fn len(&self) -> usize {
+        Type::COUNT * Type::COUNT
+    }
+
+    /// Whether the table has no type combinations, which is never the case.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Get the efficacy of a (damage, target) type combination.
+    ///
+    /// The table covers every combination, so this is always `Some`; it is
+    /// provided for symmetry with the other tables.
+    pub fn get(&self, index: (Type, Type)) -> Option<Efficacy> {
+        Some(self[index])
     }
 }
 
+impl<'a> IntoIterator for &'a EfficacyTable {
+    type Item = ((Type, Type), Efficacy);
+    type IntoIter = EfficacyIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the type combinations of an `EfficacyTable`.
+#[derive(Clone)]
+pub struct EfficacyIter<'a> {
+    table: &'a EfficacyTable,
+    next: usize,
+}
+
+impl<'a> Iterator for EfficacyIter<'a> {
+    type Item = ((Type, Type), Efficacy);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= Type::COUNT * Type::COUNT {
+            return None;
+        }
+        let damage = Type::VALUES[self.next / Type::COUNT];
+        let target = Type::VALUES[self.next % Type::COUNT];
+        self.next += 1;
+        Some(((damage, target), self.table[(damage, target)]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = Type::COUNT * Type::COUNT - self.next;
+        (left, Some(left))
+    }
+}
+
+impl<'a> ExactSizeIterator for EfficacyIter<'a> {}
+
 impl vcsv::FromCsvIncremental for EfficacyTable {
     fn from_empty_csv() -> Self { Default::default() }
 
@@ -131,3 +332,103 @@ impl std::ops::IndexMut<(Type, Type)> for EfficacyTable {
         &mut self.0[index.0.repr() as usize][index.1.repr() as usize]
     }
 }
+
+/// The efficacy of each damage type against a Pokémon's types.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DefensiveProfile(pub [CombinedEfficacy; Type::COUNT]);
+
+impl DefensiveProfile {
+    /// The profile of a Pokémon with the given ability in the generation.
+    ///
+    /// Abilities that make the Pokémon immune to a type (such as Levitate and
+    /// Flash Fire) or halve its damage (Thick Fat and Heatproof) are applied,
+    /// as is Wonder Guard. Dry Skin's extra Fire-type damage is not.
+    /// Lightningrod and Storm Drain only redirect moves before Generation V,
+    /// so do not give immunity, and abilities that do not exist yet in the
+    /// generation have no effect.
+    pub fn with_ability(
+        mut self, ability: Ability, generation: Generation
+    ) -> Self {
+        if ability.generation().repr() > generation.repr() {
+            return self;
+        }
+        let absorbs = generation.repr() >= Generation::V.repr();
+        let immunity = match ability {
+            Ability::Levitate => Some(Type::Ground),
+            Ability::FlashFire => Some(Type::Fire),
+            Ability::VoltAbsorb | Ability::MotorDrive => Some(Type::Electric),
+            Ability::Lightningrod if absorbs => Some(Type::Electric),
+            Ability::WaterAbsorb | Ability::DrySkin => Some(Type::Water),
+            Ability::StormDrain if absorbs => Some(Type::Water),
+            Ability::SapSipper => Some(Type::Grass),
+            _ => None,
+        };
+        if let Some(typ) = immunity {
+            self.0[typ.repr() as usize] = CombinedEfficacy::Not;
+        }
+        let halved: &[Type] = match ability {
+            Ability::ThickFat => &[Type::Fire, Type::Ice],
+            Ability::Heatproof => &[Type::Fire],
+            _ => &[],
+        };
+        for &typ in halved {
+            // Halving quarter damage is not representable, so is ignored.
+            let efficacy = &mut self.0[typ.repr() as usize];
+            if efficacy.repr() > CombinedEfficacy::DoubleNotVery.repr() {
+                *efficacy = CombinedEfficacy::from_repr(efficacy.repr() - 1)
+                    .unwrap_or(*efficacy);
+            }
+        }
+        if ability == Ability::WonderGuard {
+            for efficacy in self.0.iter_mut() {
+                if efficacy.repr() <= CombinedEfficacy::Regular.repr() {
+                    *efficacy = CombinedEfficacy::Not;
+                }
+            }
+        }
+        self
+    }
+
+    /// Iterate over the damage types with the given efficacy.
+    pub fn with_efficacy(
+        &self, efficacy: CombinedEfficacy
+    ) -> impl Iterator<Item = Type> + '_ {
+        self.iter().filter(move |&(_, e)| e == efficacy).map(|(t, _)| t)
+    }
+
+    /// Iterate over the damage types that do more than regular damage.
+    pub fn weaknesses(&self) -> impl Iterator<Item = Type> + '_ {
+        self.iter()
+            .filter(|&(_, e)| e.repr() > CombinedEfficacy::Regular.repr())
+            .map(|(t, _)| t)
+    }
+
+    /// Iterate over the damage types that do less than regular damage, but
+    /// still have an effect.
+    pub fn resistances(&self) -> impl Iterator<Item = Type> + '_ {
+        self.iter()
+            .filter(|&(_, e)| e == CombinedEfficacy::DoubleNotVery
+                    || e == CombinedEfficacy::NotVery)
+            .map(|(t, _)| t)
+    }
+
+    /// Iterate over the damage types that have no effect.
+    pub fn immunities(&self) -> impl Iterator<Item = Type> + '_ {
+        self.with_efficacy(CombinedEfficacy::Not)
+    }
+
+    /// Iterate over all damage types and their efficacies.
+    pub fn iter(&self) -> impl Iterator<Item = (Type, CombinedEfficacy)> + '_ {
+        Type::VALUES.iter().map(move |&t| (t, self[t]))
+    }
+}
+
+impl std::ops::Index<Type> for DefensiveProfile {
+    type Output = CombinedEfficacy;
+
+    /// Get the efficacy of a damage type.
+    fn index(&self, index: Type) -> &CombinedEfficacy {
+        &self.0[index.repr() as usize]
+    }
+}
@@ -0,0 +1,94 @@
+//! Weight in battle, and the moves whose power depends on it.
+//!
+//! Weights are in hectograms, as in `Pokemon.weight`.
+
+use crate::Ability;
+use crate::items::ItemId;
+use crate::moves::MoveId;
+
+/// The item ID of the Float Stone, which halves the holder's weight.
+pub const FLOAT_STONE: ItemId = ItemId(582);
+
+/// The move ID of Low Kick, whose power depends on the target's weight.
+pub const LOW_KICK: MoveId = MoveId(66);
+
+/// The move ID of Grass Knot, whose power depends on the target's weight.
+pub const GRASS_KNOT: MoveId = MoveId(446);
+
+/// The move ID of Heavy Slam, whose power depends on the ratio of the
+/// target's weight to the user's.
+pub const HEAVY_SLAM: MoveId = MoveId(483);
+
+/// The move ID of Heat Crash, whose power depends on the ratio of the
+/// target's weight to the user's.
+pub const HEAT_CRASH: MoveId = MoveId(534);
+
+/// The state of a Pokémon in battle that changes its weight.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct WeightModifiers {
+    /// The Pokémon's ability. Heavy Metal doubles its weight, and Light Metal
+    /// halves it.
+    pub ability: Option<Ability>,
+    /// The Pokémon's held item. A Float Stone halves its weight.
+    pub held_item: Option<ItemId>,
+    /// The number of times the Pokémon has successfully used Autotomize,
+    /// each of which reduces its weight by 100 kg.
+    pub autotomize: u8,
+}
+
+/// The weight of a Pokémon in battle.
+///
+/// Autotomize is applied first, then the ability and the Float Stone. The
+/// weight is never less than 0.1 kg.
+pub fn battle_weight(weight: u16, modifiers: &WeightModifiers) -> u16 {
+    let mut weight = (weight as u32)
+        .saturating_sub(1000 * modifiers.autotomize as u32);
+    match modifiers.ability {
+        Some(Ability::HeavyMetal) => weight *= 2,
+        Some(Ability::LightMetal) => weight /= 2,
+        _ => (),
+    }
+    if modifiers.held_item == Some(FLOAT_STONE) {
+        weight /= 2;
+    }
+    weight.clamp(1, u16::MAX as u32) as u16
+}
+
+/// The power of Low Kick and Grass Knot against a target of the given weight.
+pub fn low_kick_power(target_weight: u16) -> u8 {
+    match target_weight {
+        0..=99 => 20,
+        100..=249 => 40,
+        250..=499 => 60,
+        500..=999 => 80,
+        1000..=1999 => 100,
+        _ => 120,
+    }
+}
+
+/// The power of Heavy Slam and Heat Crash for a user and target of the given
+/// weights.
+pub fn heavy_slam_power(user_weight: u16, target_weight: u16) -> u8 {
+    let user = user_weight as u32;
+    let target = target_weight as u32;
+    match target {
+        _ if target * 5 <= user => 120,
+        _ if target * 4 <= user => 100,
+        _ if target * 3 <= user => 80,
+        _ if target * 2 <= user => 60,
+        _ => 40,
+    }
+}
+
+/// The power of a move with the given battle weights, or `None` if its power
+/// does not depend on weight.
+pub fn move_power(
+    move_id: MoveId, user_weight: u16, target_weight: u16
+) -> Option<u8> {
+    match move_id {
+        LOW_KICK | GRASS_KNOT => Some(low_kick_power(target_weight)),
+        HEAVY_SLAM | HEAT_CRASH
+            => Some(heavy_slam_power(user_weight, target_weight)),
+        _ => None,
+    }
+}
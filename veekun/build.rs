@@ -0,0 +1,45 @@
+//! Precompiles the largest CSV file into a compact binary snapshot.
+//!
+//! `pokemon_moves.csv` makes up the vast majority of the Veekun data, so it is
+//! parsed once here rather than every time the Pokédex is loaded. Each record
+//! is encoded as seven bytes, keeping the Veekun numbering:
+//!
+//! | Bytes | Field                    |
+//! |-------|--------------------------|
+//! | 0-1   | `pokemon_id` (LE)        |
+//! | 2     | `version_group_id`       |
+//! | 3-4   | `move_id` (LE)           |
+//! | 5     | `pokemon_move_method_id` |
+//! | 6     | `level`                  |
+//!
+//! Records are in the same order as in the CSV file.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+fn field<T: std::str::FromStr>(record: &csv::StringRecord, index: usize) -> T
+    where <T as std::str::FromStr>::Err: std::fmt::Debug
+{
+    let line = record.position().map_or(0, |p| p.line());
+    record[index].parse().unwrap_or_else(|e| panic!(
+        "pokemon_moves.csv line {} field {}: {:?}", line, index, e))
+}
+
+fn main() {
+    let input = Path::new("data").join("pokemon_moves.csv");
+    println!("cargo:rerun-if-changed={}", input.display());
+    let mut reader = csv::Reader::from_path(&input).unwrap();
+    let mut snapshot = Vec::new();
+    for result in reader.records() {
+        let record = result.unwrap();
+        snapshot.extend_from_slice(&field::<u16>(&record, 0).to_le_bytes());
+        snapshot.push(field(&record, 1));
+        snapshot.extend_from_slice(&field::<u16>(&record, 2).to_le_bytes());
+        snapshot.push(field(&record, 3));
+        snapshot.push(field(&record, 4));
+    }
+    let output = Path::new(&env::var("OUT_DIR").unwrap())
+        .join("pokemon_moves.bin");
+    fs::write(output, snapshot).unwrap();
+}
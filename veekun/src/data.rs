@@ -21,3 +21,69 @@ pub const SPECIES: &'static str = include_str!("../data/pokemon_species.csv");
 pub const STATS: &'static str = include_str!("../data/pokemon_stats.csv");
 pub const TYPES: &'static str = include_str!("../data/pokemon_types.csv");
 pub const EFFICACY: &'static str = include_str!("../data/type_efficacy.csv");
+
+/// The number of bytes in each record of `POKEMON_MOVES_SNAPSHOT`.
+pub const POKEMON_MOVES_RECORD_SIZE: usize = 7;
+
+/// `POKEMON_MOVES`, precompiled into a compact binary form by the build
+/// script. See `build.rs` for the format.
+pub const POKEMON_MOVES_SNAPSHOT: &[u8]
+    = include_bytes!(concat!(env!("OUT_DIR"), "/pokemon_moves.bin"));
+
+/// The file names of the Veekun CSV data, relative to the data directory.
+///
+/// Each constant has the same name as the corresponding data constant.
+pub mod files {
+    pub const BERRIES: &str = "berries.csv";
+    pub const BERRY_FLAVORS: &str = "berry_flavors.csv";
+    pub const ITEM_FLAGS: &str = "item_flag_map.csv";
+    pub const ITEMS: &str = "items.csv";
+    pub const MOVE_FLAGS: &str = "move_flag_map.csv";
+    pub const MOVE_META: &str = "move_meta.csv";
+    pub const MOVE_STAT_CHANGES: &str = "move_meta_stat_changes.csv";
+    pub const MOVES: &str = "moves.csv";
+    pub const PALACE: &str = "nature_battle_style_preferences.csv";
+    pub const POKEMON: &str = "pokemon.csv";
+    pub const ABILITIES: &str = "pokemon_abilities.csv";
+    pub const EGG_GROUPS: &str = "pokemon_egg_groups.csv";
+    pub const EVOLUTION: &str = "pokemon_evolution.csv";
+    pub const FORMS: &str = "pokemon_forms.csv";
+    pub const POKEMON_MOVES: &str = "pokemon_moves.csv";
+    pub const SPECIES: &str = "pokemon_species.csv";
+    pub const STATS: &str = "pokemon_stats.csv";
+    pub const TYPES: &str = "pokemon_types.csv";
+    pub const EFFICACY: &str = "type_efficacy.csv";
+
+    /// All of the file names.
+    pub const ALL: &[&str] = &[
+        BERRIES, BERRY_FLAVORS, ITEM_FLAGS, ITEMS, MOVE_FLAGS, MOVE_META,
+        MOVE_STAT_CHANGES, MOVES, PALACE, POKEMON, ABILITIES, EGG_GROUPS,
+        EVOLUTION, FORMS, POKEMON_MOVES, SPECIES, STATS, TYPES, EFFICACY,
+    ];
+}
+
+/// Get the included data for a file name in `files`, if there is any.
+pub fn get(file: &str) -> Option<&'static str> {
+    match file {
+        files::BERRIES => Some(BERRIES),
+        files::BERRY_FLAVORS => Some(BERRY_FLAVORS),
+        files::ITEM_FLAGS => Some(ITEM_FLAGS),
+        files::ITEMS => Some(ITEMS),
+        files::MOVE_FLAGS => Some(MOVE_FLAGS),
+        files::MOVE_META => Some(MOVE_META),
+        files::MOVE_STAT_CHANGES => Some(MOVE_STAT_CHANGES),
+        files::MOVES => Some(MOVES),
+        files::PALACE => Some(PALACE),
+        files::POKEMON => Some(POKEMON),
+        files::ABILITIES => Some(ABILITIES),
+        files::EGG_GROUPS => Some(EGG_GROUPS),
+        files::EVOLUTION => Some(EVOLUTION),
+        files::FORMS => Some(FORMS),
+        files::POKEMON_MOVES => Some(POKEMON_MOVES),
+        files::SPECIES => Some(SPECIES),
+        files::STATS => Some(STATS),
+        files::TYPES => Some(TYPES),
+        files::EFFICACY => Some(EFFICACY),
+        _ => None,
+    }
+}
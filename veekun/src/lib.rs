@@ -16,3 +16,40 @@ pub fn to_pascal_case(s: &str) -> String {
     }
     builder
 }
+
+/// Convert a `PascalCase` identifier to Veekun-standard `kebab-case`.
+///
+/// Runs of capitals are kept together as one word, so `"OneHitKO"` becomes
+/// `"one-hit-ko"` and `"XDShadow"` becomes `"xd-shadow"`.
+pub fn to_kebab_case(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut builder = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1)
+                .is_some_and(|n| n.is_lowercase());
+            if !prev.is_uppercase() || next_lower {
+                builder.push('-');
+            }
+        }
+        builder.extend(c.to_lowercase());
+    }
+    builder
+}
+
+/// The characters of a name that are significant for comparison: the
+/// alphanumeric characters, lowercased.
+pub fn name_key(s: &str) -> impl Iterator<Item = char> + '_ {
+    s.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase)
+}
+
+/// Compare two names, ignoring case and any characters that are not
+/// alphanumeric.
+///
+/// This allows Veekun `kebab-case` identifiers, `PascalCase` names, and names
+/// as written in the games to match each other: `"thunder-punch"`,
+/// `"ThunderPunch"`, and `"Thunder Punch"` are all the same name.
+pub fn names_match(a: &str, b: &str) -> bool {
+    name_key(a).eq(name_key(b))
+}